@@ -0,0 +1,64 @@
+//! Rebuild an audit log from exported verification events
+//!
+//! Reads one base64 `ContractEvent` XDR per line on stdin, as found in the
+//! Soroban transaction metadata of a ledger export, and prints one CSV row
+//! per verification event. Other events are skipped. Pass the verifier's
+//! contract address to keep only its events:
+//!
+//! ```bash
+//! cargo run --example audit_log -- CBPB...43OI < events.txt
+//! ```
+
+use std::io::{self, BufRead};
+
+use soroban_groth16_verifier::events::decode_event;
+use soroban_sdk::xdr::{ContractEvent, Hash, Limits, ReadXdr, ScAddress};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn main() {
+    let verifier = std::env::args().nth(1);
+
+    println!("ledger,contract,vk_id,verified,error_code,inputs_hash,invoker");
+    for (number, line) in io::stdin().lock().lines().enumerate() {
+        let line = line.expect("failed to read stdin");
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let event = match ContractEvent::from_xdr_base64(line, Limits::none()) {
+            Ok(event) => event,
+            Err(error) => {
+                eprintln!("line {}: not a ContractEvent: {}", number + 1, error);
+                continue;
+            }
+        };
+        let Some(record) = decode_event(&event) else {
+            continue;
+        };
+
+        let contract = ScAddress::Contract(Hash(record.contract_id)).to_string();
+        if verifier
+            .as_ref()
+            .is_some_and(|verifier| *verifier != contract)
+        {
+            continue;
+        }
+        println!(
+            "{},{},{},{},{},{},{}",
+            record.ledger,
+            contract,
+            hex(&record.vk_id),
+            record.verified,
+            record.error_code,
+            hex(&record.inputs_hash),
+            record
+                .invoker
+                .map(|invoker| invoker.to_string())
+                .unwrap_or_default()
+        );
+    }
+}
@@ -0,0 +1,130 @@
+// Admin and Roles
+// `initialize` sets the admin once. The admin upgrades the contract, hands
+// the admin role over, and grants the roles that gate the verifying-key
+// registry and the spending of nullifiers. The admin holds every role
+// without a grant.
+//
+// Only the admin lives in instance storage, which every invocation loads;
+// grants are persistent entries, so adding them does not make other calls
+// dearer. Every entrypoint extends the instance (see `extend_instance_ttl`),
+// and a grant is extended whenever it is checked.
+
+use soroban_sdk::{contracttype, Address, Env};
+
+use crate::registry::LEDGERS_PER_DAY;
+use crate::VerifierError;
+
+/// The instance and grants are extended whenever used with less than
+/// `TTL_THRESHOLD` ledgers left, up to `TTL_EXTEND_TO`
+pub(crate) const TTL_THRESHOLD: u32 = 30 * LEDGERS_PER_DAY;
+pub(crate) const TTL_EXTEND_TO: u32 = 120 * LEDGERS_PER_DAY;
+
+/// Permission to manage the verifying-key registry
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Role {
+    /// May register verifying keys
+    Registrar,
+    /// May deprecate registered verifying keys
+    Deprecator,
+    /// May spend nullifiers with `verify_and_consume` and `consume_batch`
+    Consumer,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum AccessKey {
+    /// In instance storage
+    Admin,
+    /// In persistent storage, present when the role is granted to the account
+    Grant(Address, Role),
+}
+
+/// Keep the contract instance, and with it the admin, alive
+pub(crate) fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+/// Set the first admin, who has to authorize it
+///
+/// Fails if the contract is already initialized. SDK 21 contracts have no
+/// constructor, and a transaction runs a single host function, so a
+/// deployment leaves the contract open until this call lands: whoever calls
+/// it first becomes the admin, with the power to upgrade the code. Deploy
+/// through a factory contract that deploys and initializes in one
+/// invocation, or check `admin` afterwards and abandon an instance that
+/// someone else claimed.
+pub(crate) fn initialize(env: &Env, admin: &Address) -> Result<(), VerifierError> {
+    let storage = env.storage().instance();
+    if storage.has(&AccessKey::Admin) {
+        return Err(VerifierError::AlreadyInitialized);
+    }
+    admin.require_auth();
+    storage.set(&AccessKey::Admin, admin);
+    Ok(())
+}
+
+/// The current admin, if the contract is initialized
+pub(crate) fn admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&AccessKey::Admin)
+}
+
+/// Require the admin's authorization
+pub(crate) fn require_admin(env: &Env) -> Result<Address, VerifierError> {
+    let admin = admin(env).ok_or(VerifierError::NotInitialized)?;
+    admin.require_auth();
+    Ok(admin)
+}
+
+/// Hand the admin role to `new_admin`
+///
+/// Both admins authorize the change, so it cannot go to an address nobody
+/// controls.
+pub(crate) fn set_admin(env: &Env, new_admin: &Address) -> Result<(), VerifierError> {
+    require_admin(env)?;
+    new_admin.require_auth();
+    env.storage().instance().set(&AccessKey::Admin, new_admin);
+    Ok(())
+}
+
+pub(crate) fn grant_role(env: &Env, account: &Address, role: Role) -> Result<(), VerifierError> {
+    require_admin(env)?;
+    let storage = env.storage().persistent();
+    let key = AccessKey::Grant(account.clone(), role);
+    storage.set(&key, &());
+    storage.extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    Ok(())
+}
+
+pub(crate) fn revoke_role(env: &Env, account: &Address, role: Role) -> Result<(), VerifierError> {
+    require_admin(env)?;
+    env.storage()
+        .persistent()
+        .remove(&AccessKey::Grant(account.clone(), role));
+    Ok(())
+}
+
+/// Whether `account` is the admin or was granted `role`
+pub(crate) fn has_role(env: &Env, account: &Address, role: Role) -> bool {
+    if admin(env).as_ref() == Some(account) {
+        return true;
+    }
+    let storage = env.storage().persistent();
+    let key = AccessKey::Grant(account.clone(), role);
+    if !storage.has(&key) {
+        return false;
+    }
+    storage.extend_ttl(&key, TTL_THRESHOLD, TTL_EXTEND_TO);
+    true
+}
+
+/// Require `caller`'s authorization and `role`
+pub(crate) fn require_role(env: &Env, caller: &Address, role: Role) -> Result<(), VerifierError> {
+    caller.require_auth();
+    if !has_role(env, caller, role) {
+        return Err(VerifierError::MissingRole);
+    }
+    Ok(())
+}
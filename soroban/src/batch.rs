@@ -0,0 +1,365 @@
+// Batch Verification
+// `verify_batch` checks n proofs against one verifying key with a single
+// random linear combination of their equations:
+//
+//   Π e(r_i·A_i, B_i) · e(-Σ r_i·L_i, γ) · e(-Σ r_i·C_i, δ) = e(α, β)^(Σ r_i)
+//
+// If every proof verifies, this holds. If one does not, it holds with
+// probability about 2^-128 over the r_i. It takes n + 2 Miller loops, the
+// last two over the prepared lines of γ and δ, and one final
+// exponentiation, rather than 3n Miller loops (three per proof on the
+// prepared path) and n final exponentiations.
+//
+// Σ r_i·L_i is taken as one MSM over IC: with L_i = IC[0] + Σ_j x_ij·IC[j+1],
+// it equals (Σ r_i)·IC[0] + Σ_j (Σ_i r_i·x_ij)·IC[j+1], so the public inputs
+// are folded into one scalar per IC point instead of running an MSM per proof.
+//
+// The r_i are 128-bit values hashed from the key ID and the XDR encoding of
+// every proof and public input. Changing any proof changes every r_i, so a
+// prover cannot pick invalid proofs whose errors cancel. `env.prng()` is
+// not used because its seed is not bound to the proofs.
+//
+// If the combined check fails, each proof is checked on its own, with its
+// own L_i, to find the ones that fail. Like `verify`, the batch entrypoints only read nullifiers;
+// `consume_batch` spends them, for a caller holding `Role::Consumer`.
+
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Bytes, BytesN, Env, Vec};
+
+use crate::curve::{G1Affine, G1Projective, G2Affine};
+use crate::msm::g1_msm;
+use crate::pairing::{multi_pairing_prepared, PreparedPair};
+use crate::scalar::Fr;
+use crate::{
+    nullifier, registry, validated, G1Point, Groth16Verifier, PreparedVerifyingKey, ProofData,
+    VerifierError, VerifyingKey, MAX_BATCH, MSM_BATCH,
+};
+
+/// Domain separator of the transcript the r_i are derived from
+const TRANSCRIPT_TAG: &[u8] = b"openzktool/groth16-bn254/batch/v1";
+
+/// A decoded proof whose public inputs passed `validated::check_inputs`
+#[derive(Clone, Copy)]
+struct Entry {
+    a: G1Affine,
+    b: G2Affine,
+    c: G1Affine,
+}
+
+/// Per-proof codes, 0 for a proof that verified, and the nullifiers of the
+/// proofs that carry one
+struct Outcome {
+    results: Vec<u32>,
+    nullifiers: [Option<BytesN<32>>; MAX_BATCH],
+}
+
+/// Verify `proofs[i]` against `public_inputs[i]` for every i, with the
+/// registered key `vk_id`
+///
+/// Returns one code per proof: 0 if it verified, otherwise its
+/// `VerifierError` code. Fails as a whole only for the key, or when the
+/// two vectors differ in length or hold more than `MAX_BATCH` entries.
+/// Writes nothing: proofs whose nullifier is already spent fail, but a
+/// value repeated within the batch is accepted each time.
+pub(crate) fn verify_batch(
+    env: &Env,
+    vk_id: &BytesN<32>,
+    proofs: &Vec<ProofData>,
+    public_inputs: &Vec<Vec<Bytes>>,
+) -> Result<Vec<u32>, VerifierError> {
+    check_shape(proofs, public_inputs)?;
+    let pvk = registry::prepared_key(env, vk_id)?;
+    Ok(check_batch(env, vk_id, &pvk, true, proofs, public_inputs)?.results)
+}
+
+/// `verify_batch`, then spend the nullifiers of the accepted proofs
+///
+/// Nullifiers are spent in batch order, so a value repeated within the
+/// batch is accepted once and later copies fail with `NullifierAlreadyUsed`.
+pub(crate) fn consume_batch(
+    env: &Env,
+    vk_id: &BytesN<32>,
+    proofs: &Vec<ProofData>,
+    public_inputs: &Vec<Vec<Bytes>>,
+) -> Result<Vec<u32>, VerifierError> {
+    check_shape(proofs, public_inputs)?;
+    let pvk = registry::prepared_key(env, vk_id)?;
+    let Outcome {
+        mut results,
+        nullifiers,
+    } = check_batch(env, vk_id, &pvk, true, proofs, public_inputs)?;
+
+    for (i, nullifier) in nullifiers.iter().enumerate() {
+        let Some(nullifier) = nullifier else {
+            continue;
+        };
+        if results.get(i as u32) != Some(0) {
+            continue;
+        }
+        if nullifier::is_spent(env, vk_id, nullifier) {
+            results.set(i as u32, VerifierError::NullifierAlreadyUsed as u32);
+        } else {
+            nullifier::spend(env, vk_id, nullifier);
+        }
+    }
+
+    Ok(results)
+}
+
+/// `verify_batch` against a verifying key given with the call
+///
+/// The key is validated and prepared once for the whole batch, and fails
+/// the call if it is rejected. Nullifiers only apply to registered keys, so
+/// none are checked.
+pub(crate) fn verify_proof_batch(
+    env: &Env,
+    vk: &VerifyingKey,
+    proofs: &Vec<ProofData>,
+    public_inputs: &Vec<Vec<Bytes>>,
+) -> Result<Vec<u32>, VerifierError> {
+    check_shape(proofs, public_inputs)?;
+    let pvk = Groth16Verifier::prepare_vk(env, vk)?;
+    // Only defined for a validated key, which `prepare_vk` checked
+    let vk_id = registry::key_id(env, vk);
+    Ok(check_batch(env, &vk_id, &pvk, false, proofs, public_inputs)?.results)
+}
+
+fn check_shape(
+    proofs: &Vec<ProofData>,
+    public_inputs: &Vec<Vec<Bytes>>,
+) -> Result<(), VerifierError> {
+    if proofs.len() != public_inputs.len() {
+        return Err(VerifierError::BatchLengthMismatch);
+    }
+    if proofs.len() as usize > MAX_BATCH {
+        return Err(VerifierError::BatchTooLarge);
+    }
+    Ok(())
+}
+
+/// Check every proof against the prepared key with ID `vk_id`, reading the
+/// key's nullifiers if `with_nullifiers`
+fn check_batch(
+    env: &Env,
+    vk_id: &BytesN<32>,
+    pvk: &PreparedVerifyingKey,
+    with_nullifiers: bool,
+    proofs: &Vec<ProofData>,
+    public_inputs: &Vec<Vec<Bytes>>,
+) -> Result<Outcome, VerifierError> {
+    let key = validated::PreparedKey::decode(env, pvk)?;
+
+    // 1. Decode every proof; those that fail here are left out of the pairing
+    let mut outcome = Outcome {
+        results: Vec::new(env),
+        nullifiers: core::array::from_fn(|_| None),
+    };
+    let mut entries = [None; MAX_BATCH];
+    for (i, (proof, inputs)) in proofs.iter().zip(public_inputs.iter()).enumerate() {
+        let nullifier = if with_nullifiers {
+            nullifier::designated(env, vk_id, &inputs)
+        } else {
+            Ok(None)
+        };
+        let decoded = nullifier.and_then(|nullifier| {
+            let proof = validated::Proof::decode(env, &proof)?;
+            validated::check_inputs(&pvk.ic, &inputs)?;
+            Ok((
+                Entry {
+                    a: proof.a,
+                    b: proof.b,
+                    c: proof.c,
+                },
+                nullifier,
+            ))
+        });
+        match decoded {
+            Ok((entry, nullifier)) => {
+                entries[i] = Some(entry);
+                outcome.nullifiers[i] = nullifier;
+                outcome.results.push_back(0);
+            }
+            Err(error) => outcome.results.push_back(error as u32),
+        }
+    }
+
+    // 2. One combined check, or one check per proof to find the failures
+    let seed = transcript_seed(env, vk_id, proofs, public_inputs);
+    let entries = &entries[..proofs.len() as usize];
+    match check_combined(env, &key, &pvk.ic, public_inputs, entries, &seed) {
+        Ok(()) => {}
+        Err(VerifierError::PairingCheckFailed) => {
+            for (i, (entry, inputs)) in entries.iter().zip(public_inputs.iter()).enumerate() {
+                if let Some(entry) = entry {
+                    if let Err(error) = check_one(env, &key, &pvk.ic, &inputs, entry) {
+                        outcome.results.set(i as u32, error as u32);
+                    }
+                }
+            }
+        }
+        Err(error) => return Err(error),
+    }
+
+    Ok(outcome)
+}
+
+/// SHA-256 over the tag, the key ID, and the XDR of the proofs and inputs
+///
+/// XDR encodes every length, so distinct batches cannot share a transcript.
+fn transcript_seed(
+    env: &Env,
+    vk_id: &BytesN<32>,
+    proofs: &Vec<ProofData>,
+    public_inputs: &Vec<Vec<Bytes>>,
+) -> [u8; 32] {
+    let mut transcript = Bytes::from_slice(env, TRANSCRIPT_TAG);
+    transcript.append(&vk_id.clone().into());
+    transcript.append(&proofs.clone().to_xdr(env));
+    transcript.append(&public_inputs.clone().to_xdr(env));
+    env.crypto().sha256(&transcript).to_array()
+}
+
+/// r_i: the first 16 bytes of SHA-256(seed || i), never 0
+fn batch_scalar(env: &Env, seed: &[u8; 32], index: u32) -> u128 {
+    let mut preimage = Bytes::from_array(env, seed);
+    preimage.extend_from_array(&index.to_be_bytes());
+    let digest = env.crypto().sha256(&preimage).to_array();
+    let mut head = [0u8; 16];
+    head.copy_from_slice(&digest[..16]);
+    u128::from_be_bytes(head).max(1)
+}
+
+/// The combined equation over the entries that decoded
+///
+/// Fails with `PairingCheckFailed` if it does not hold.
+fn check_combined(
+    env: &Env,
+    key: &validated::PreparedKey,
+    ic: &Vec<G1Point>,
+    public_inputs: &Vec<Vec<Bytes>>,
+    entries: &[Option<Entry>],
+    seed: &[u8; 32],
+) -> Result<(), VerifierError> {
+    let mut pairs = [(G1Affine::infinity(), G2Affine::infinity()); MAX_BATCH];
+    let mut scaled = [G1Projective::identity(); MAX_BATCH];
+    let mut weights = [None; MAX_BATCH];
+    let mut cs = [G1Affine::infinity(); MAX_BATCH];
+    let mut scalars = [[0u64; 4]; MAX_BATCH];
+    // Σ r_i as an integer: e(α, β) has order r, so it need not be reduced
+    let mut exponent = [0u64; 4];
+
+    let mut len = 0;
+    for (i, entry) in entries.iter().enumerate() {
+        let Some(entry) = entry else {
+            continue;
+        };
+        let r = batch_scalar(env, seed, i as u32);
+        let limbs = [r as u64, (r >> 64) as u64, 0, 0];
+        let weight = Fr::reduce(&limbs);
+
+        scaled[len] = G1Projective::mul_glv(&entry.a, &weight);
+        pairs[len].1 = entry.b;
+        weights[i] = Some(weight);
+        cs[len] = entry.c;
+        scalars[len] = limbs;
+        add_u128(&mut exponent, r);
+        len += 1;
+    }
+    if len == 0 {
+        return Ok(());
+    }
+
+    let mut scaled_a = [G1Affine::infinity(); MAX_BATCH];
+    G1Projective::batch_to_affine(&scaled[..len], &mut scaled_a[..len]);
+    for (pair, a) in pairs.iter_mut().zip(scaled_a.iter()) {
+        pair.0 = *a;
+    }
+    let l = folded_commitment(env, ic, public_inputs, &weights[..entries.len()])?.to_affine();
+    let c = g1_msm(&cs[..len], &scalars[..len]).to_affine();
+
+    let mut gamma_lines = key.gamma_lines.iter().copied();
+    let mut delta_lines = key.delta_lines.iter().copied();
+    let mut prepared: [PreparedPair; 2] =
+        [(l.neg(), &mut gamma_lines), (c.neg(), &mut delta_lines)];
+
+    // Every stream holds exactly MILLER_LINE_COUNT lines after decoding
+    match multi_pairing_prepared(&pairs[..len], &mut prepared) {
+        Some(gt) if gt == key.alpha_beta.cyclotomic_pow(&exponent) => Ok(()),
+        Some(_) => Err(VerifierError::PairingCheckFailed),
+        None => Err(VerifierError::MalformedVerifyingKey),
+    }
+}
+
+/// Σ r_i·L_i over the entries with a weight r_i
+///
+/// Folds the inputs into Σ_i r_i·x_ij for each IC point, then runs a single
+/// MSM over IC, `MSM_BATCH` points at a time like
+/// `validated::input_commitment`. Every weighted entry passed
+/// `validated::check_inputs`, so its inputs decode and match IC in number.
+fn folded_commitment(
+    env: &Env,
+    ic: &Vec<G1Point>,
+    public_inputs: &Vec<Vec<Bytes>>,
+    weights: &[Option<Fr>],
+) -> Result<G1Projective, VerifierError> {
+    let mut result = G1Projective::identity();
+    let mut points = [G1Affine::infinity(); MSM_BATCH];
+    let mut scalars = [[0u64; 4]; MSM_BATCH];
+
+    let mut start = 0;
+    while start < ic.len() {
+        let end = ic.len().min(start + MSM_BATCH as u32);
+        for j in start..end {
+            let mut folded = Fr::zero();
+            for (weight, inputs) in weights.iter().zip(public_inputs.iter()) {
+                let Some(weight) = weight else {
+                    continue;
+                };
+                // IC[0] has coefficient 1 in every L_i
+                let x = match j {
+                    0 => Fr::one(),
+                    _ => Groth16Verifier::bytes_to_scalar(&inputs.get(j - 1).unwrap())?,
+                };
+                folded = folded.add(&weight.mul(&x));
+            }
+            points[(j - start) as usize] = validated::ic_point(env, &ic.get(j).unwrap())?;
+            scalars[(j - start) as usize] = folded.to_limbs();
+        }
+
+        let len = (end - start) as usize;
+        result = result.add(&g1_msm(&points[..len], &scalars[..len]));
+        start = end;
+    }
+
+    Ok(result)
+}
+
+/// The equation of a single entry, as `verify` checks it
+fn check_one(
+    env: &Env,
+    key: &validated::PreparedKey,
+    ic: &Vec<G1Point>,
+    public_inputs: &Vec<Bytes>,
+    entry: &Entry,
+) -> Result<(), VerifierError> {
+    let l = validated::input_commitment(env, ic, public_inputs)?;
+    let mut gamma_lines = key.gamma_lines.iter().copied();
+    let mut delta_lines = key.delta_lines.iter().copied();
+    let mut prepared: [PreparedPair; 2] = [
+        (l.neg(), &mut gamma_lines),
+        (entry.c.neg(), &mut delta_lines),
+    ];
+
+    match multi_pairing_prepared(&[(entry.a, entry.b)], &mut prepared) {
+        Some(gt) if gt == key.alpha_beta => Ok(()),
+        Some(_) => Err(VerifierError::PairingCheckFailed),
+        None => Err(VerifierError::MalformedVerifyingKey),
+    }
+}
+
+fn add_u128(acc: &mut [u64; 4], value: u128) {
+    let (low, carry) = (acc[0] as u128 | (acc[1] as u128) << 64).overflowing_add(value);
+    acc[0] = low as u64;
+    acc[1] = (low >> 64) as u64;
+    acc[2] += carry as u64;
+}
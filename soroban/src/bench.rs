@@ -0,0 +1,62 @@
+// Stage Benchmarks
+// Only built with the `stage-bench` feature, for the WASM budget test in
+// tests.rs. `bench_stages` runs the first few stages of `verify_proof`, so
+// the metered cost of one stage is the difference between two calls that
+// stop on either side of it. Release builds do not export it.
+
+use core::hint::black_box;
+
+use soroban_sdk::{contractimpl, Bytes, Env, Vec};
+
+use crate::pairing::{final_exponentiation, multi_miller_loop};
+use crate::{
+    validated, Groth16Verifier, Groth16VerifierClient, ProofData, VerifierError, VerifyingKey,
+};
+
+#[contractimpl]
+impl Groth16Verifier {
+    /// Run the first `stages` stages of `verify_proof`
+    ///
+    /// The stages are: decoding with its subgroup checks, the four G2
+    /// subgroup checks again on their own, the MSM, the Miller loop and the
+    /// final exponentiation. Each result is passed through `black_box`, so
+    /// a stage is not optimized away when the next one does not run.
+    pub fn bench_stages(
+        env: Env,
+        proof: ProofData,
+        vk: VerifyingKey,
+        public_inputs: Vec<Bytes>,
+        stages: u32,
+    ) -> Result<(), VerifierError> {
+        if stages == 0 {
+            return Ok(());
+        }
+        let proof = black_box(validated::Proof::decode(&env, &proof)?);
+        let key = black_box(validated::Key::decode(&env, &vk)?);
+
+        if stages == 1 {
+            return Ok(());
+        }
+        for point in [proof.b, key.beta, key.gamma, key.delta].iter() {
+            black_box(validated::check_g2(black_box(point)))?;
+        }
+
+        if stages == 2 {
+            return Ok(());
+        }
+        let vk_x = black_box(validated::input_commitment(&env, &vk.ic, &public_inputs)?);
+
+        if stages == 3 {
+            return Ok(());
+        }
+        let f = black_box(multi_miller_loop(&Self::groth16_pairs(&proof, &key, &vk_x)));
+
+        if stages == 4 {
+            return Ok(());
+        }
+        if !black_box(final_exponentiation(&f)).is_one() {
+            return Err(VerifierError::PairingCheckFailed);
+        }
+        Ok(())
+    }
+}
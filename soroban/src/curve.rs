@@ -3,15 +3,79 @@
 // G2 curve: y^2 = x^3 + 3/(9+u) over Fq2
 
 use crate::field::{Fq, Fq2};
+use crate::glv;
+use crate::scalar::{self, Fr};
+
+// G2 twist coefficient b' = 3/(9+u) (Montgomery form)
+// = 19485874751759354771024239261021720505790618469301721065564631296452457478373
+//   + 266929791119991161246907387137283842545076965332900288569378510910307636690*u
+pub const G2_B: Fq2 = Fq2::new(
+    Fq::from_montgomery([
+        0x3bf938e377b802a8,
+        0x020b1b273633535d,
+        0x26b7edf049755260,
+        0x2514c6324384a86d,
+    ]),
+    Fq::from_montgomery([
+        0x38e7ecccd1dcff67,
+        0x65f0b37d93ce0d3e,
+        0xd749d0dd22ac00aa,
+        0x0141b9ce4a688d4d,
+    ]),
+);
 
 // BN254 subgroup order (scalar field modulus)
 // r = 21888242871839275222246405745257275088548364400416034343698204186575808495617
-pub const SUBGROUP_ORDER: [u64; 4] = [
-    0x43e1f593f0000001,
-    0x2833e84879b97091,
-    0xb85045b68181585d,
-    0x30644e72e131a029,
-];
+#[cfg_attr(not(test), allow(dead_code))]
+pub const SUBGROUP_ORDER: [u64; 4] = scalar::MODULUS;
+
+// ψ coefficient ξ^((p-1)/3) for the x-coordinate (Montgomery form)
+pub const PSI_COEFF_X: Fq2 = Fq2::new(
+    Fq::from_montgomery([
+        0xb5773b104563ab30,
+        0x347f91c8a9aa6454,
+        0x7a007127242e0991,
+        0x1956bcd8118214ec,
+    ]),
+    Fq::from_montgomery([
+        0x6e849f1ea0aa4757,
+        0xaa1c7b6d89f89141,
+        0xb6e713cdfae0ca3a,
+        0x26694fbb4e82ebc3,
+    ]),
+);
+
+// ψ coefficient ξ^((p-1)/2) for the y-coordinate (Montgomery form)
+pub const PSI_COEFF_Y: Fq2 = Fq2::new(
+    Fq::from_montgomery([
+        0xe4bbdd0c2936b629,
+        0xbb30f162e133bacb,
+        0x31a9d1b6f9645366,
+        0x253570bea500f8dd,
+    ]),
+    Fq::from_montgomery([
+        0xa1d77ce45ffe77c7,
+        0x07affd117826d1db,
+        0x6d16bd27bb7edc6b,
+        0x2c87200285defecc,
+    ]),
+);
+
+// ψ² coefficient ξ^((p^2-1)/3) for the x-coordinate, which lies in Fq.
+// The matching y coefficient ξ^((p^2-1)/2) is -1.
+pub const PSI2_COEFF_X: Fq = Fq::from_montgomery([
+    0x3350c88e13e80b9c,
+    0x7dce557cdb5e56b9,
+    0x6001b4b8b615564a,
+    0x2682e617020217e0,
+]);
+
+// G1 endomorphism coefficient β, a primitive cube root of unity in Fq. It
+// is the same element as the ψ² coefficient above.
+pub const G1_ENDO_BETA: Fq = PSI2_COEFF_X;
+
+// 6u^2 = 0x6f4d8248eeb859fbf83e9682e87cfd46 as u64 limbs (little-endian)
+const SIX_U_SQUARED: [u64; 4] = [0xf83e9682e87cfd46, 0x6f4d8248eeb859fb, 0, 0];
 
 // G1 Point (affine coordinates)
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -44,12 +108,13 @@ impl G1Affine {
 
     /// BN254 G1 generator point
     /// This is a standard generator for the BN254 curve
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn generator() -> Self {
-        // BN254 G1 generator coordinates (in Montgomery form)
+        // BN254 G1 generator coordinates
         // x = 1
         // y = 2
-        let x = Fq::from_montgomery([1, 0, 0, 0]);
-        let y = Fq::from_montgomery([2, 0, 0, 0]);
+        let x = Fq::one();
+        let y = Fq::one().add(&Fq::one());
         G1Affine::new(x, y)
     }
 
@@ -127,21 +192,34 @@ impl G1Affine {
         G1Affine::new(x3, y3)
     }
 
-    /// Scalar multiplication using double-and-add
-    pub fn mul(&self, scalar: &[u64; 4]) -> G1Affine {
-        let mut result = G1Affine::infinity();
-        let mut temp = *self;
+    /// Scalar multiplication by an element of Fr
+    ///
+    /// Uses the GLV endomorphism, so it relies on the point being in G1: for
+    /// a point off the curve the result need not match `mul_limbs`.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn mul(&self, scalar: &Fr) -> G1Affine {
+        G1Projective::mul_glv(self, scalar).to_affine()
+    }
 
-        for limb in scalar.iter() {
-            for bit in 0..64 {
-                if (limb >> bit) & 1 == 1 {
-                    result = result.add(&temp);
-                }
-                temp = temp.double();
-            }
+    /// φ: (x, y) -> (β * x, y), which acts on G1 as multiplication by λ
+    /// (`glv::LAMBDA`)
+    pub fn endomorphism(&self) -> G1Affine {
+        if self.infinity {
+            return *self;
         }
+        G1Affine::new(self.x.mul(&G1_ENDO_BETA), self.y)
+    }
 
-        result
+    /// Scalar multiplication by a raw integer (little-endian limbs),
+    /// computed in Jacobian coordinates
+    ///
+    /// Unlike `mul`, the integer is not reduced mod r, so multiples such as
+    /// [r]P can be formed for subgroup checks.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn mul_limbs(&self, scalar: &[u64; 4]) -> G1Affine {
+        G1Projective::from_affine(self)
+            .mul_limbs(scalar)
+            .to_affine()
     }
 
     /// Negate point
@@ -159,6 +237,7 @@ impl G1Affine {
     /// completeness and to validate the point is valid.
     ///
     /// Verification: [r]P = O (where r is the subgroup order)
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn is_in_correct_subgroup(&self) -> bool {
         if self.infinity {
             return true;
@@ -171,6 +250,283 @@ impl G1Affine {
     }
 }
 
+// G1 Point (Jacobian coordinates)
+//
+// (X, Y, Z) represents the affine point (X/Z^2, Y/Z^3); Z = 0 is the point
+// at infinity. Group operations need no field inversion, so a scalar
+// multiplication pays for a single inversion in `to_affine`.
+#[derive(Clone, Copy, Debug)]
+pub struct G1Projective {
+    pub x: Fq,
+    pub y: Fq,
+    pub z: Fq,
+}
+
+impl G1Projective {
+    pub const fn new(x: Fq, y: Fq, z: Fq) -> Self {
+        G1Projective { x, y, z }
+    }
+
+    pub const fn identity() -> Self {
+        G1Projective::new(Fq::zero(), Fq::one(), Fq::zero())
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn from_affine(point: &G1Affine) -> Self {
+        if point.infinity {
+            return G1Projective::identity();
+        }
+        G1Projective::new(point.x, point.y, Fq::one())
+    }
+
+    /// Convert back to affine coordinates (one field inversion)
+    pub fn to_affine(self) -> G1Affine {
+        let z_inv = match self.z.inverse() {
+            Some(z_inv) => z_inv,
+            None => return G1Affine::infinity(),
+        };
+        let z_inv2 = z_inv.square();
+        let z_inv3 = z_inv2.mul(&z_inv);
+
+        G1Affine::new(self.x.mul(&z_inv2), self.y.mul(&z_inv3))
+    }
+
+    /// Convert many points to affine with a single field inversion
+    ///
+    /// Montgomery's trick as in `Fq::batch_inverse`, with the running
+    /// products of the Z coordinates kept in `out` until they are replaced
+    /// by the converted points.
+    pub fn batch_to_affine(points: &[G1Projective], out: &mut [G1Affine]) {
+        assert_eq!(points.len(), out.len());
+
+        let mut acc = Fq::one();
+        for (point, prefix) in points.iter().zip(out.iter_mut()) {
+            prefix.x = acc;
+            if !point.is_identity() {
+                acc = acc.mul(&point.z);
+            }
+        }
+
+        let mut acc_inv = acc.inverse().unwrap();
+        for (point, affine) in points.iter().zip(out.iter_mut()).rev() {
+            if point.is_identity() {
+                *affine = G1Affine::infinity();
+                continue;
+            }
+            let z_inv = affine.x.mul(&acc_inv);
+            acc_inv = acc_inv.mul(&point.z);
+
+            let z_inv2 = z_inv.square();
+            let z_inv3 = z_inv2.mul(&z_inv);
+            *affine = G1Affine::new(point.x.mul(&z_inv2), point.y.mul(&z_inv3));
+        }
+    }
+
+    /// Point doubling (dbl-2009-l, a = 0)
+    pub fn double(&self) -> G1Projective {
+        if self.is_identity() {
+            return *self;
+        }
+
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+
+        // D = 2((X + B)^2 - A - C)
+        let d = self.x.add(&b).square().sub(&a).sub(&c);
+        let d = d.add(&d);
+
+        // E = 3A, F = E^2
+        let e = a.add(&a).add(&a);
+        let f = e.square();
+
+        // X3 = F - 2D
+        let x3 = f.sub(&d.add(&d));
+
+        // Y3 = E(D - X3) - 8C
+        let c2 = c.add(&c);
+        let c4 = c2.add(&c2);
+        let c8 = c4.add(&c4);
+        let y3 = e.mul(&d.sub(&x3)).sub(&c8);
+
+        // Z3 = 2 * Y * Z
+        let yz = self.y.mul(&self.z);
+        let z3 = yz.add(&yz);
+
+        G1Projective::new(x3, y3, z3)
+    }
+
+    /// Point addition (add-2007-bl)
+    pub fn add(&self, other: &G1Projective) -> G1Projective {
+        if self.is_identity() {
+            return *other;
+        }
+        if other.is_identity() {
+            return *self;
+        }
+
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        let u1 = self.x.mul(&z2z2);
+        let u2 = other.x.mul(&z1z1);
+        let s1 = self.y.mul(&other.z).mul(&z2z2);
+        let s2 = other.y.mul(&self.z).mul(&z1z1);
+
+        let h = u2.sub(&u1);
+        let r = s2.sub(&s1);
+
+        // Same x: either the same point or its negation
+        if h.is_zero() {
+            if r.is_zero() {
+                return self.double();
+            }
+            return G1Projective::identity();
+        }
+
+        // I = (2H)^2, J = H * I, r = 2(S2 - S1), V = U1 * I
+        let i = h.add(&h).square();
+        let j = h.mul(&i);
+        let r = r.add(&r);
+        let v = u1.mul(&i);
+
+        // X3 = r^2 - J - 2V
+        let x3 = r.square().sub(&j).sub(&v.add(&v));
+
+        // Y3 = r(V - X3) - 2 * S1 * J
+        let s1j = s1.mul(&j);
+        let y3 = r.mul(&v.sub(&x3)).sub(&s1j.add(&s1j));
+
+        // Z3 = ((Z1 + Z2)^2 - Z1Z1 - Z2Z2) * H
+        let z3 = self.z.add(&other.z).square().sub(&z1z1).sub(&z2z2).mul(&h);
+
+        G1Projective::new(x3, y3, z3)
+    }
+
+    /// Mixed addition with an affine point (madd-2007-bl, Z2 = 1)
+    pub fn add_affine(&self, other: &G1Affine) -> G1Projective {
+        if other.infinity {
+            return *self;
+        }
+        if self.is_identity() {
+            return G1Projective::from_affine(other);
+        }
+
+        let z1z1 = self.z.square();
+        let u2 = other.x.mul(&z1z1);
+        let s2 = other.y.mul(&self.z).mul(&z1z1);
+
+        let h = u2.sub(&self.x);
+        let r = s2.sub(&self.y);
+
+        // Same x: either the same point or its negation
+        if h.is_zero() {
+            if r.is_zero() {
+                return self.double();
+            }
+            return G1Projective::identity();
+        }
+
+        // HH = H^2, I = 4HH, J = H * I, r = 2(S2 - Y1), V = X1 * I
+        let hh = h.square();
+        let i = hh.add(&hh);
+        let i = i.add(&i);
+        let j = h.mul(&i);
+        let r = r.add(&r);
+        let v = self.x.mul(&i);
+
+        // X3 = r^2 - J - 2V
+        let x3 = r.square().sub(&j).sub(&v.add(&v));
+
+        // Y3 = r(V - X3) - 2 * Y1 * J
+        let y1j = self.y.mul(&j);
+        let y3 = r.mul(&v.sub(&x3)).sub(&y1j.add(&y1j));
+
+        // Z3 = (Z1 + H)^2 - Z1Z1 - HH
+        let z3 = self.z.add(&h).square().sub(&z1z1).sub(&hh);
+
+        G1Projective::new(x3, y3, z3)
+    }
+
+    /// Negate point
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn neg(&self) -> G1Projective {
+        G1Projective::new(self.x, self.y.neg(), self.z)
+    }
+
+    /// Scalar multiplication by an element of Fr
+    ///
+    /// Like `G1Affine::mul`, only valid for points of G1.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn mul(&self, scalar: &Fr) -> G1Projective {
+        Self::mul_glv(&self.to_affine(), scalar)
+    }
+
+    /// [k]P as [k1]P + [k2]φ(P) for the half-size split of `glv::decompose`
+    ///
+    /// A joint double-and-add over the 128-bit halves: half the doublings
+    /// of `mul_limbs`, and mixed additions of P, φ(P) or their sum.
+    pub fn mul_glv(point: &G1Affine, scalar: &Fr) -> G1Projective {
+        let [(neg1, k1), (neg2, k2)] = glv::decompose(&scalar.to_limbs());
+        let p1 = if neg1 { point.neg() } else { *point };
+        let p2 = point.endomorphism();
+        let p2 = if neg2 { p2.neg() } else { p2 };
+        let p12 = p1.add(&p2);
+
+        let mut result = G1Projective::identity();
+        let bits = u128::BITS - (k1 | k2).leading_zeros();
+        for bit in (0..bits).rev() {
+            result = result.double();
+            match ((k1 >> bit) & 1, (k2 >> bit) & 1) {
+                (1, 0) => result = result.add_affine(&p1),
+                (0, 1) => result = result.add_affine(&p2),
+                (1, 1) => result = result.add_affine(&p12),
+                _ => {}
+            }
+        }
+
+        result
+    }
+
+    /// Scalar multiplication by a raw integer (little-endian limbs),
+    /// double-and-add from the most significant bit
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn mul_limbs(&self, scalar: &[u64; 4]) -> G1Projective {
+        let mut result = G1Projective::identity();
+
+        for limb in scalar.iter().rev() {
+            for bit in (0..64).rev() {
+                result = result.double();
+                if (limb >> bit) & 1 == 1 {
+                    result = result.add(self);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl PartialEq for G1Projective {
+    /// Compare the represented points: X1 * Z2^2 == X2 * Z1^2 and
+    /// Y1 * Z2^3 == Y2 * Z1^3
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_identity() || other.is_identity() {
+            return self.is_identity() && other.is_identity();
+        }
+
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+
+        self.x.mul(&z2z2) == other.x.mul(&z1z1)
+            && self.y.mul(&z2z2).mul(&other.z) == other.y.mul(&z1z1).mul(&self.z)
+    }
+}
+
+impl Eq for G1Projective {}
+
 // G2 Point (affine coordinates over Fq2)
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct G2Affine {
@@ -202,11 +558,41 @@ impl G2Affine {
 
     /// BN254 G2 generator point
     /// This is a standard generator for the BN254 curve on G2
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn generator() -> Self {
-        // BN254 G2 generator coordinates (simplified for testing)
-        // In production, use the actual BN254 G2 generator
-        let x = Fq2::new(Fq::from_montgomery([1, 0, 0, 0]), Fq::from_montgomery([1, 0, 0, 0]));
-        let y = Fq2::new(Fq::from_montgomery([2, 0, 0, 0]), Fq::from_montgomery([2, 0, 0, 0]));
+        // BN254 G2 generator coordinates (Montgomery form)
+        // x = 10857046999023057135944570762232829481370756359578518086990519993285655852781
+        //   + 11559732032986387107991004021392285783925812861821192530917403151452391805634*u
+        // y = 8495653923123431417604973247489272438418190587263600148770280649306958101930
+        //   + 4082367875863433681332203403145435568316851327593401208105741076214120093531*u
+        let x = Fq2::new(
+            Fq::from_montgomery([
+                0x8e83b5d102bc2026,
+                0xdceb1935497b0172,
+                0xfbb8264797811adf,
+                0x19573841af96503b,
+            ]),
+            Fq::from_montgomery([
+                0xafb4737da84c6140,
+                0x6043dd5a5802d8c4,
+                0x09e950fc52a02f86,
+                0x14fef0833aea7b6b,
+            ]),
+        );
+        let y = Fq2::new(
+            Fq::from_montgomery([
+                0x619dfa9d886be9f6,
+                0xfe7fd297f59e9b78,
+                0xff9e1a62231b7dfe,
+                0x28fd7eebae9e4206,
+            ]),
+            Fq::from_montgomery([
+                0x64095b56c71856ee,
+                0xdc57f922327d3cbb,
+                0x55f935be33351076,
+                0x0da4a0e693fd6482,
+            ]),
+        );
         G2Affine::new(x, y)
     }
 
@@ -217,19 +603,16 @@ impl G2Affine {
             return true;
         }
 
-        // b' = 3/(9+u) = 3(9-u)/(81+1) = (27-3u)/82
-        // In Fq2: b' = 19485874751759354771024239261021720505790618469301721065564631296452457478373 +
-        //              266929791119991161246907387137283842545076965332900288569378510910307636690*u
-
+        // b' = 3/(9+u) = 3(9-u)/(81+1) = (27-3u)/82, see G2_B
         let y2 = self.y.square();
         let x3 = self.x.square().mul(&self.x);
+        let rhs = x3.add(&G2_B);
 
-        // Simplified: for now just check coordinates are non-zero
-        // Full implementation would use the correct b' constant
-        !x3.is_zero() && !y2.is_zero()
+        y2 == rhs
     }
 
     /// Point addition
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn add(&self, other: &G2Affine) -> G2Affine {
         if self.infinity {
             return *other;
@@ -257,43 +640,37 @@ impl G2Affine {
     }
 
     /// Point doubling
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn double(&self) -> G2Affine {
         if self.infinity {
             return *self;
         }
 
-        let three_fq = Fq::from_montgomery([3, 0, 0, 0]);
-        let three = Fq2::new(three_fq, Fq::zero());
-        let two_fq = Fq::from_montgomery([2, 0, 0, 0]);
-        let two = Fq2::new(two_fq, Fq::zero());
-
+        // λ = (3x^2) / (2y)
         let x_sq = self.x.square();
-        let numerator = three.mul(&x_sq);
-        let denominator = two.mul(&self.y);
+        let numerator = x_sq.add(&x_sq).add(&x_sq);
+        let denominator = self.y.add(&self.y);
         let lambda = numerator.mul(&denominator.inverse().unwrap());
 
-        let two_x = two.mul(&self.x);
+        let two_x = self.x.add(&self.x);
         let x3 = lambda.square().sub(&two_x);
         let y3 = lambda.mul(&self.x.sub(&x3)).sub(&self.y);
 
         G2Affine::new(x3, y3)
     }
 
-    /// Scalar multiplication
-    pub fn mul(&self, scalar: &[u64; 4]) -> G2Affine {
-        let mut result = G2Affine::infinity();
-        let mut temp = *self;
-
-        for limb in scalar.iter() {
-            for bit in 0..64 {
-                if (limb >> bit) & 1 == 1 {
-                    result = result.add(&temp);
-                }
-                temp = temp.double();
-            }
-        }
+    /// Scalar multiplication by an element of Fr
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn mul(&self, scalar: &Fr) -> G2Affine {
+        self.mul_limbs(&scalar.to_limbs())
+    }
 
-        result
+    /// Scalar multiplication by a raw integer (little-endian limbs)
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn mul_limbs(&self, scalar: &[u64; 4]) -> G2Affine {
+        G2Projective::from_affine(self)
+            .mul_limbs(scalar)
+            .to_affine()
     }
 
     /// Negate point
@@ -313,24 +690,264 @@ impl G2Affine {
     /// Without this check, an attacker could provide points from
     /// a different subgroup, potentially breaking proof soundness.
     ///
-    /// Verification: [r]P = O (where r is the subgroup order)
+    /// ψ acts as multiplication by p on G2, and p = r + 6u² for BN curves,
+    /// so every subgroup point satisfies ψ(P) = [6u²]P.
     ///
-    /// This ensures P is in the r-torsion subgroup, which is required
-    /// for Groth16 security on BN254.
+    /// Verification: ψ(P) = [6u²]P, which holds exactly for the points
+    /// of the r-torsion subgroup on the BN254 twist (El Housni, Guillevic,
+    /// Piellard, "Co-factor clearing and subgroup membership testing on
+    /// pairing-friendly curves", eprint 2022/348). This replaces a 254-bit
+    /// [r]P with a 127-bit multiplication and one ψ.
     pub fn is_in_correct_subgroup(&self) -> bool {
         if self.infinity {
             return true;
         }
 
-        // Compute [r]P where r is the subgroup order
-        let r_times_p = self.mul(&SUBGROUP_ORDER);
+        let six_u2_p = G2Projective::from_affine(self).mul_limbs(&SIX_U_SQUARED);
+        G2Projective::from_affine(&self.psi()) == six_u2_p
+    }
+
+    /// ψ: the untwist-Frobenius-twist endomorphism
+    ///
+    /// (x, y) -> (x̄ * ξ^((p-1)/3), ȳ * ξ^((p-1)/2)). On G2 this acts as
+    /// multiplication by p, i.e. as the p-power Frobenius π on E(Fq12).
+    pub fn psi(&self) -> G2Affine {
+        if self.infinity {
+            return *self;
+        }
+        G2Affine::new(
+            self.x.frobenius_map(1).mul(&PSI_COEFF_X),
+            self.y.frobenius_map(1).mul(&PSI_COEFF_Y),
+        )
+    }
 
-        // Check if result is the identity (infinity point)
-        // If [r]P = O, then P is in the subgroup of order r
-        r_times_p.is_infinity()
+    /// ψ²: (x, y) -> (x * ξ^((p^2-1)/3), -y)
+    pub fn psi2(&self) -> G2Affine {
+        if self.infinity {
+            return *self;
+        }
+        G2Affine::new(
+            Fq2::new(self.x.c0.mul(&PSI2_COEFF_X), self.x.c1.mul(&PSI2_COEFF_X)),
+            self.y.neg(),
+        )
     }
 }
 
+// G2 Point (Jacobian coordinates over Fq2), same representation as G1Projective
+#[derive(Clone, Copy, Debug)]
+pub struct G2Projective {
+    pub x: Fq2,
+    pub y: Fq2,
+    pub z: Fq2,
+}
+
+impl G2Projective {
+    pub const fn new(x: Fq2, y: Fq2, z: Fq2) -> Self {
+        G2Projective { x, y, z }
+    }
+
+    pub const fn identity() -> Self {
+        G2Projective::new(Fq2::zero(), Fq2::one(), Fq2::zero())
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn from_affine(point: &G2Affine) -> Self {
+        if point.infinity {
+            return G2Projective::identity();
+        }
+        G2Projective::new(point.x, point.y, Fq2::one())
+    }
+
+    /// Convert back to affine coordinates (one field inversion)
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn to_affine(self) -> G2Affine {
+        let z_inv = match self.z.inverse() {
+            Some(z_inv) => z_inv,
+            None => return G2Affine::infinity(),
+        };
+        let z_inv2 = z_inv.square();
+        let z_inv3 = z_inv2.mul(&z_inv);
+
+        G2Affine::new(self.x.mul(&z_inv2), self.y.mul(&z_inv3))
+    }
+
+    /// Point doubling (dbl-2009-l, a = 0)
+    pub fn double(&self) -> G2Projective {
+        if self.is_identity() {
+            return *self;
+        }
+
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+
+        // D = 2((X + B)^2 - A - C)
+        let d = self.x.add(&b).square().sub(&a).sub(&c);
+        let d = d.add(&d);
+
+        // E = 3A, F = E^2
+        let e = a.add(&a).add(&a);
+        let f = e.square();
+
+        // X3 = F - 2D
+        let x3 = f.sub(&d.add(&d));
+
+        // Y3 = E(D - X3) - 8C
+        let c2 = c.add(&c);
+        let c4 = c2.add(&c2);
+        let c8 = c4.add(&c4);
+        let y3 = e.mul(&d.sub(&x3)).sub(&c8);
+
+        // Z3 = 2 * Y * Z
+        let yz = self.y.mul(&self.z);
+        let z3 = yz.add(&yz);
+
+        G2Projective::new(x3, y3, z3)
+    }
+
+    /// Point addition (add-2007-bl)
+    pub fn add(&self, other: &G2Projective) -> G2Projective {
+        if self.is_identity() {
+            return *other;
+        }
+        if other.is_identity() {
+            return *self;
+        }
+
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+        let u1 = self.x.mul(&z2z2);
+        let u2 = other.x.mul(&z1z1);
+        let s1 = self.y.mul(&other.z).mul(&z2z2);
+        let s2 = other.y.mul(&self.z).mul(&z1z1);
+
+        let h = u2.sub(&u1);
+        let r = s2.sub(&s1);
+
+        // Same x: either the same point or its negation
+        if h.is_zero() {
+            if r.is_zero() {
+                return self.double();
+            }
+            return G2Projective::identity();
+        }
+
+        // I = (2H)^2, J = H * I, r = 2(S2 - S1), V = U1 * I
+        let i = h.add(&h).square();
+        let j = h.mul(&i);
+        let r = r.add(&r);
+        let v = u1.mul(&i);
+
+        // X3 = r^2 - J - 2V
+        let x3 = r.square().sub(&j).sub(&v.add(&v));
+
+        // Y3 = r(V - X3) - 2 * S1 * J
+        let s1j = s1.mul(&j);
+        let y3 = r.mul(&v.sub(&x3)).sub(&s1j.add(&s1j));
+
+        // Z3 = ((Z1 + Z2)^2 - Z1Z1 - Z2Z2) * H
+        let z3 = self.z.add(&other.z).square().sub(&z1z1).sub(&z2z2).mul(&h);
+
+        G2Projective::new(x3, y3, z3)
+    }
+
+    /// Mixed addition with an affine point (madd-2007-bl, Z2 = 1)
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn add_affine(&self, other: &G2Affine) -> G2Projective {
+        if other.infinity {
+            return *self;
+        }
+        if self.is_identity() {
+            return G2Projective::from_affine(other);
+        }
+
+        let z1z1 = self.z.square();
+        let u2 = other.x.mul(&z1z1);
+        let s2 = other.y.mul(&self.z).mul(&z1z1);
+
+        let h = u2.sub(&self.x);
+        let r = s2.sub(&self.y);
+
+        // Same x: either the same point or its negation
+        if h.is_zero() {
+            if r.is_zero() {
+                return self.double();
+            }
+            return G2Projective::identity();
+        }
+
+        // HH = H^2, I = 4HH, J = H * I, r = 2(S2 - Y1), V = X1 * I
+        let hh = h.square();
+        let i = hh.add(&hh);
+        let i = i.add(&i);
+        let j = h.mul(&i);
+        let r = r.add(&r);
+        let v = self.x.mul(&i);
+
+        // X3 = r^2 - J - 2V
+        let x3 = r.square().sub(&j).sub(&v.add(&v));
+
+        // Y3 = r(V - X3) - 2 * Y1 * J
+        let y1j = self.y.mul(&j);
+        let y3 = r.mul(&v.sub(&x3)).sub(&y1j.add(&y1j));
+
+        // Z3 = (Z1 + H)^2 - Z1Z1 - HH
+        let z3 = self.z.add(&h).square().sub(&z1z1).sub(&hh);
+
+        G2Projective::new(x3, y3, z3)
+    }
+
+    /// Negate point
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn neg(&self) -> G2Projective {
+        G2Projective::new(self.x, self.y.neg(), self.z)
+    }
+
+    /// Scalar multiplication by an element of Fr
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn mul(&self, scalar: &Fr) -> G2Projective {
+        self.mul_limbs(&scalar.to_limbs())
+    }
+
+    /// Scalar multiplication by a raw integer (little-endian limbs),
+    /// double-and-add from the most significant bit
+    pub fn mul_limbs(&self, scalar: &[u64; 4]) -> G2Projective {
+        let mut result = G2Projective::identity();
+
+        for limb in scalar.iter().rev() {
+            for bit in (0..64).rev() {
+                result = result.double();
+                if (limb >> bit) & 1 == 1 {
+                    result = result.add(self);
+                }
+            }
+        }
+
+        result
+    }
+}
+
+impl PartialEq for G2Projective {
+    /// Compare the represented points: X1 * Z2^2 == X2 * Z1^2 and
+    /// Y1 * Z2^3 == Y2 * Z1^3
+    fn eq(&self, other: &Self) -> bool {
+        if self.is_identity() || other.is_identity() {
+            return self.is_identity() && other.is_identity();
+        }
+
+        let z1z1 = self.z.square();
+        let z2z2 = other.z.square();
+
+        self.x.mul(&z2z2) == other.x.mul(&z1z1)
+            && self.y.mul(&z2z2).mul(&other.z) == other.y.mul(&z1z1).mul(&self.z)
+    }
+}
+
+impl Eq for G2Projective {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -388,12 +1005,52 @@ mod tests {
     #[test]
     fn test_g2_subgroup_check_generator() {
         // Generator should be in the correct subgroup
-        // Note: This test assumes generator() returns a valid point
-        // In production, use the actual BN254 G2 generator
         let g = G2Affine::generator();
-        // We can't assert this without the real generator,
-        // but the method should not panic
-        let _ = g.is_in_correct_subgroup();
+        assert!(g.is_in_correct_subgroup());
+    }
+
+    #[test]
+    fn test_g2_generator_is_on_curve() {
+        let g = G2Affine::generator();
+        assert!(g.is_on_curve());
+        assert!(g.neg().is_on_curve());
+    }
+
+    #[test]
+    fn test_g2_group_law_stays_on_curve() {
+        let g = G2Affine::generator();
+        let g2 = g.double();
+        let g3 = g2.add(&g);
+        assert!(g2.is_on_curve());
+        assert!(g3.is_on_curve());
+        assert_eq!(g3, g.add(&g2));
+        assert_eq!(g.mul(&Fr::from_u64(3)), g3);
+    }
+
+    #[test]
+    fn test_g2_off_curve_points_rejected() {
+        let g = G2Affine::generator();
+
+        // y + 1
+        let bumped_y = G2Affine::new(g.x, g.y.add(&Fq2::one()));
+        assert!(!bumped_y.is_on_curve());
+
+        // x with its Fq2 components swapped (EIP-197 ordering mistake)
+        let swapped_x = G2Affine::new(Fq2::new(g.x.c1, g.x.c0), g.y);
+        assert!(!swapped_x.is_on_curve());
+
+        // both coordinates with components swapped
+        let swapped = G2Affine::new(Fq2::new(g.x.c1, g.x.c0), Fq2::new(g.y.c1, g.y.c0));
+        assert!(!swapped.is_on_curve());
+
+        // (0, 0) is not a curve point unless flagged as infinity
+        let zero = G2Affine::new(Fq2::zero(), Fq2::zero());
+        assert!(!zero.is_on_curve());
+
+        // G1 generator (1, 2) embedded in Fq2 satisfies y^2 = x^3 + 3, not the twist
+        let two = Fq::one().add(&Fq::one());
+        let g1_embedded = G2Affine::new(Fq2::one(), Fq2::new(two, Fq::zero()));
+        assert!(!g1_embedded.is_on_curve());
     }
 
     #[test]
@@ -402,7 +1059,7 @@ mod tests {
         let g = G2Affine::generator();
 
         // If generator is in the correct subgroup, [r]G should be infinity
-        let r_times_g = g.mul(&SUBGROUP_ORDER);
+        let r_times_g = g.mul_limbs(&SUBGROUP_ORDER);
 
         // This test will only pass if generator() returns a point
         // in the correct subgroup
@@ -416,9 +1073,265 @@ mod tests {
     fn test_g1_scalar_mul_by_r_gives_infinity() {
         // Property: For any point P in G1, [r]P = O (since cofactor=1)
         let g = G1Affine::generator();
-        let r_times_g = g.mul(&SUBGROUP_ORDER);
+        let r_times_g = g.mul_limbs(&SUBGROUP_ORDER);
 
         assert!(r_times_g.is_infinity(),
                 "G1 point multiplied by r should give infinity");
     }
+
+    #[test]
+    fn test_g1_projective_matches_affine() {
+        let g = G1Affine::generator();
+        let g_proj = G1Projective::from_affine(&g);
+
+        let g2 = g.double();
+        let g3 = g2.add(&g);
+        assert_eq!(g_proj.double().to_affine(), g2);
+        assert_eq!(g_proj.double().add(&g_proj).to_affine(), g3);
+        assert_eq!(g_proj.double().add_affine(&g).to_affine(), g3);
+        assert_eq!(g_proj.mul(&Fr::from_u64(3)).to_affine(), g3);
+        assert_eq!(g_proj.to_affine(), g);
+    }
+
+    #[test]
+    fn test_g1_projective_exceptional_cases() {
+        let g = G1Affine::generator();
+        let g_proj = G1Projective::from_affine(&g);
+        let identity = G1Projective::identity();
+
+        // P + P takes the doubling path, P + (-P) = O
+        let g2 = g_proj.double();
+        assert_eq!(g2.add(&g2), g2.double());
+        assert_eq!(g2.add_affine(&g2.to_affine()), g2.double());
+        assert!(g2.add(&g2.neg()).is_identity());
+        assert!(g_proj.add_affine(&g.neg()).is_identity());
+
+        // O is neutral on both sides, and maps to affine infinity
+        assert_eq!(identity.add(&g2), g2);
+        assert_eq!(g2.add(&identity), g2);
+        assert_eq!(identity.add_affine(&g), g_proj);
+        assert_eq!(g_proj.add_affine(&G1Affine::infinity()), g_proj);
+        assert!(identity.double().is_identity());
+        assert!(identity.to_affine().is_infinity());
+        assert!(G1Projective::from_affine(&G1Affine::infinity()).is_identity());
+    }
+
+    #[test]
+    fn test_g1_projective_eq_ignores_scaling() {
+        // (X, Y, Z) and (λ²X, λ³Y, λZ) are the same point
+        let p = G1Projective::from_affine(&G1Affine::generator()).double();
+        let lambda = Fq::from_montgomery([5, 6, 7, 8]);
+        let lambda2 = lambda.square();
+        let scaled = G1Projective::new(
+            p.x.mul(&lambda2),
+            p.y.mul(&lambda2).mul(&lambda),
+            p.z.mul(&lambda),
+        );
+        assert_eq!(scaled, p);
+        assert_ne!(scaled, p.neg());
+        assert_eq!(scaled.to_affine(), p.to_affine());
+    }
+
+    #[test]
+    fn test_g1_batch_to_affine() {
+        let g_proj = G1Projective::from_affine(&G1Affine::generator());
+        let mut points = [G1Projective::identity(); 6];
+        for i in 1..points.len() {
+            points[i] = points[i - 1].double().add(&g_proj);
+        }
+        // Identities at the start and in the middle
+        points[3] = G1Projective::identity();
+
+        let mut affine = [G1Affine::generator(); 6];
+        G1Projective::batch_to_affine(&points, &mut affine);
+        for (point, affine) in points.iter().zip(affine.iter()) {
+            assert_eq!(*affine, point.to_affine());
+        }
+
+        let mut affine = [G1Affine::generator(); 2];
+        G1Projective::batch_to_affine(&[G1Projective::identity(); 2], &mut affine);
+        assert!(affine.iter().all(|p| p.is_infinity()));
+    }
+
+    #[test]
+    fn test_g1_endomorphism_is_lambda() {
+        let g = G1Affine::generator();
+        for p in [g, g.mul_limbs(&[5, 0, 0, 0])] {
+            assert_eq!(p.endomorphism(), p.mul_limbs(&glv::LAMBDA));
+            assert!(p.endomorphism().is_on_curve());
+        }
+        assert!(G1Affine::infinity().endomorphism().is_infinity());
+    }
+
+    #[test]
+    fn test_g1_glv_mul_matches_double_and_add() {
+        let g = G1Affine::generator();
+        let lambda = Fr::from_limbs(&glv::LAMBDA).unwrap();
+        let half = Fr::from_u64(2).inverse().unwrap();
+        let two_128 = Fr::from_limbs(&[0, 0, 1, 0]).unwrap();
+
+        // 0, 1, r - 1, and scalars next to λ, (r + 1) / 2 and 2^128, where
+        // the decomposition's rounding changes
+        let one = Fr::one();
+        let scalars = [
+            Fr::zero(),
+            one,
+            one.neg(),
+            lambda.sub(&one),
+            lambda,
+            lambda.add(&one),
+            half.sub(&one),
+            half,
+            half.add(&one),
+            two_128.sub(&one),
+            two_128,
+            two_128.add(&one),
+            Fr::from_limbs(&[0x0123456789abcdef, 0xfedcba9876543210, 7, 0x1000]).unwrap(),
+        ];
+
+        for p in [g, g.mul_limbs(&[5, 0, 0, 0]), G1Affine::infinity()] {
+            for k in scalars.iter() {
+                let expected = p.mul_limbs(&k.to_limbs());
+                assert_eq!(p.mul(k), expected, "k = {:?}", k);
+                assert_eq!(G1Projective::from_affine(&p).mul(k).to_affine(), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_g1_projective_mul() {
+        let g = G1Projective::from_affine(&G1Affine::generator());
+        assert!(g.mul_limbs(&SUBGROUP_ORDER).is_identity());
+        assert!(g.mul(&Fr::zero()).is_identity());
+
+        // [a]([b]G) = [a·b]G with multi-limb scalars
+        let a = Fr::from_limbs(&[0x0123456789abcdef, 0xfedcba9876543210, 0, 0]).unwrap();
+        let b = Fr::from_limbs(&[7, 0, 0, 0x1000]).unwrap();
+        assert_eq!(g.mul(&b).mul(&a), g.mul(&a.mul(&b)));
+    }
+
+    #[test]
+    fn test_g2_projective_matches_affine() {
+        let g = G2Affine::generator();
+        let g_proj = G2Projective::from_affine(&g);
+
+        let g2 = g.double();
+        let g3 = g2.add(&g);
+        assert_eq!(g_proj.double().to_affine(), g2);
+        assert_eq!(g_proj.double().add(&g_proj).to_affine(), g3);
+        assert_eq!(g_proj.double().add_affine(&g).to_affine(), g3);
+        assert_eq!(g_proj.mul(&Fr::from_u64(3)).to_affine(), g3);
+
+        let g2_proj = g_proj.double();
+        assert_eq!(g2_proj.add(&g2_proj), g2_proj.double());
+        assert!(g2_proj.add(&g2_proj.neg()).is_identity());
+        assert!(g_proj.add_affine(&g.neg()).is_identity());
+        assert!(g_proj.mul_limbs(&SUBGROUP_ORDER).is_identity());
+    }
+
+    /// Decode a twist point from big-endian hex coordinates [x0, x1, y0, y1]
+    fn g2_from_hex(coords: [&str; 4]) -> G2Affine {
+        let mut fq = [Fq::zero(); 4];
+        for (out, hex) in fq.iter_mut().zip(coords.iter()) {
+            let mut bytes = [0u8; 32];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap();
+            }
+            *out = Fq::from_bytes_be(&bytes).unwrap();
+        }
+        G2Affine::new(Fq2::new(fq[0], fq[1]), Fq2::new(fq[2], fq[3]))
+    }
+
+    /// Points on the twist y² = x³ + b' outside the order-r subgroup
+    fn non_subgroup_twist_points() -> [G2Affine; 4] {
+        [
+            // x = 2 + u
+            g2_from_hex([
+                "0000000000000000000000000000000000000000000000000000000000000002",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "101f7278419308b95099eca02dcee0c5381f4d26d1d62313f057167f064101ce",
+                "2b76c179599bb92a963dac85546a005a777f7c13f6a7b75d5918b6b5808f5fde",
+            ]),
+            // x = 3 + u
+            g2_from_hex([
+                "0000000000000000000000000000000000000000000000000000000000000003",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "18317863a822ced60b87aca78e89515cb87f54f50c1600e4512dc35743fa5543",
+                "16f36623ff7c6a394eb9190bb391072c1e41899e5fd5bf84d7132ea39ab5ab0c",
+            ]),
+            // [r](2 + u, ...): a pure cofactor-torsion point
+            g2_from_hex([
+                "26ef37aea86e322b6c5d69c5e85fa82af327f489db750f2b017bf7592324f244",
+                "0df07790e16e5e7aa7001ab738c21f9084bf5c693085e47f942fa0aad2a83207",
+                "1e573608f009c378624806e5ce79142df1652823838b3ffd58fb15f9444d8157",
+                "1ce612c9d3ff4338c62b348c64d533e732615683faea8e6e9ce648f03b04ffc2",
+            ]),
+            // G2 generator plus the torsion point above
+            g2_from_hex([
+                "2c374b3d173e8ed0543c2f4dcfd3082c85e34a1cbee99cc29d14b27d6e3dff95",
+                "1ad48d656d9fb2788490858352444c8708914f8422bd815516d73d7b18ba3243",
+                "0a00e21ec3ea5e341e454370d9ee21811dc81d90cb630c41a9ab63cd4f4a9bb6",
+                "09e62a3670cd38d4cd99d32892eda989cd66510864631a42b4e5f160a0c460ca",
+            ]),
+        ]
+    }
+
+    #[test]
+    fn test_six_u_squared_is_p_minus_r() {
+        let mut diff = [0u64; 4];
+        let mut borrow = 0i128;
+        for i in 0..4 {
+            borrow = crate::field::MODULUS[i] as i128 - SUBGROUP_ORDER[i] as i128 - borrow;
+            diff[i] = borrow as u64;
+            borrow = if borrow < 0 { 1 } else { 0 };
+        }
+        assert_eq!(diff, SIX_U_SQUARED);
+    }
+
+    #[test]
+    fn test_psi_on_g2() {
+        let g = G2Affine::generator();
+        let psi_g = g.psi();
+        assert!(psi_g.is_on_curve());
+        assert_eq!(psi_g, g.mul_limbs(&SIX_U_SQUARED));
+        assert_eq!(g.psi2(), psi_g.psi());
+
+        // ψ is a group homomorphism
+        let g2 = g.double();
+        assert_eq!(g2.psi(), psi_g.double());
+        assert!(G2Affine::infinity().psi().is_infinity());
+    }
+
+    #[test]
+    fn test_g2_subgroup_check_rejects_non_subgroup_twist_points() {
+        // h = 2p - r, the G2 cofactor
+        let cofactor = [
+            0x345f2299c0f9fa8d,
+            0x06ceecda572a2489,
+            0xb85045b68181585e,
+            0x30644e72e131a029,
+        ];
+
+        for point in non_subgroup_twist_points() {
+            assert!(point.is_on_curve());
+            assert!(!point.is_in_correct_subgroup());
+            // Cross-check against the definition [r]P = O
+            assert!(!point.mul_limbs(&SUBGROUP_ORDER).is_infinity());
+
+            // Clearing the cofactor lands in the subgroup
+            let cleared = point.mul_limbs(&cofactor);
+            assert!(cleared.is_in_correct_subgroup());
+            assert!(cleared.mul_limbs(&SUBGROUP_ORDER).is_infinity());
+        }
+    }
+
+    #[test]
+    fn test_g2_subgroup_check_accepts_multiples_of_generator() {
+        let g = G2Affine::generator();
+        for k in [1u64, 2, 0xdead_beef, u64::MAX] {
+            let point = g.mul(&Fr::from_u64(k));
+            assert!(point.is_in_correct_subgroup());
+            assert!(point.neg().is_in_correct_subgroup());
+        }
+    }
 }
@@ -0,0 +1,139 @@
+// Verification Events
+// `verify_logged` publishes one event per verification, accepted or not, so
+// that indexers and auditors can follow verifications from the ledger:
+//
+//   topics: ("verify", vk_id: BytesN<32>, verified: bool)
+//   data:   VerificationRecord
+//
+// The data is a map keyed by field name (see `VerificationRecord`).
+// `decode_event` turns an exported event back into an `AuditRecord`; it only
+// exists in host builds, where the XDR types are available.
+
+use soroban_sdk::{contracttype, symbol_short, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+/// First topic of every verification event
+pub const VERIFY_TOPIC: Symbol = symbol_short!("verify");
+
+/// Data of a verification event
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerificationRecord {
+    /// 0 if the proof verified, otherwise the `VerifierError` code
+    pub error_code: u32,
+    /// SHA-256 of the public inputs concatenated as submitted
+    pub inputs_hash: BytesN<32>,
+    /// The address that authorized the call, when one was given
+    pub invoker: Option<Address>,
+    /// Sequence number of the ledger the verification ran in
+    pub ledger: u32,
+}
+
+/// SHA-256 of the public inputs concatenated as submitted
+///
+/// Valid inputs are 32 bytes each, so the hash identifies them; for
+/// malformed ones it only serves as a fingerprint.
+pub(crate) fn inputs_hash(env: &Env, public_inputs: &Vec<Bytes>) -> BytesN<32> {
+    let mut encoding = Bytes::new(env);
+    for input in public_inputs.iter() {
+        encoding.append(&input);
+    }
+    env.crypto().sha256(&encoding).into()
+}
+
+pub(crate) fn publish_verification(
+    env: &Env,
+    vk_id: &BytesN<32>,
+    error_code: u32,
+    inputs_hash: BytesN<32>,
+    invoker: Option<Address>,
+) {
+    let record = VerificationRecord {
+        error_code,
+        inputs_hash,
+        invoker,
+        ledger: env.ledger().sequence(),
+    };
+    env.events()
+        .publish((VERIFY_TOPIC, vk_id.clone(), error_code == 0), record);
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub use decode::{decode_event, AuditRecord};
+
+#[cfg(not(target_family = "wasm"))]
+mod decode {
+    use soroban_sdk::xdr::{
+        ContractEvent, ContractEventBody, ContractEventType, ScAddress, ScMap, ScSymbol, ScVal,
+    };
+
+    /// A verification event read back from the ledger
+    #[derive(Clone, Debug, Eq, PartialEq)]
+    pub struct AuditRecord {
+        /// The verifier contract that published the event
+        pub contract_id: [u8; 32],
+        pub vk_id: [u8; 32],
+        pub verified: bool,
+        /// 0 if the proof verified, otherwise the `VerifierError` code
+        pub error_code: u32,
+        pub inputs_hash: [u8; 32],
+        pub invoker: Option<ScAddress>,
+        pub ledger: u32,
+    }
+
+    /// Decode a verification event, as found in transaction metadata
+    ///
+    /// Returns `None` for any other event, so a whole ledger export can be
+    /// filtered through it; check `contract_id` against the verifier's
+    /// address, since any contract can publish events with these topics.
+    pub fn decode_event(event: &ContractEvent) -> Option<AuditRecord> {
+        if event.type_ != ContractEventType::Contract {
+            return None;
+        }
+        let contract_id = event.contract_id.as_ref()?.0;
+        let ContractEventBody::V0(body) = &event.body;
+
+        let [ScVal::Symbol(topic), ScVal::Bytes(vk_id), ScVal::Bool(verified)] =
+            body.topics.as_slice()
+        else {
+            return None;
+        };
+        if topic.as_slice() != b"verify" {
+            return None;
+        }
+
+        let ScVal::Map(Some(data)) = &body.data else {
+            return None;
+        };
+        let ScVal::U32(error_code) = field(data, "error_code")? else {
+            return None;
+        };
+        let ScVal::Bytes(inputs_hash) = field(data, "inputs_hash")? else {
+            return None;
+        };
+        let invoker = match field(data, "invoker")? {
+            ScVal::Void => None,
+            ScVal::Address(address) => Some(address.clone()),
+            _ => return None,
+        };
+        let ScVal::U32(ledger) = field(data, "ledger")? else {
+            return None;
+        };
+
+        Some(AuditRecord {
+            contract_id,
+            vk_id: vk_id.as_slice().try_into().ok()?,
+            verified: *verified,
+            error_code: *error_code,
+            inputs_hash: inputs_hash.as_slice().try_into().ok()?,
+            invoker,
+            ledger: *ledger,
+        })
+    }
+
+    fn field<'a>(map: &'a ScMap, name: &str) -> Option<&'a ScVal> {
+        let name = ScSymbol(name.try_into().ok()?);
+        map.iter()
+            .find(|entry| entry.key == ScVal::Symbol(name.clone()))
+            .map(|entry| &entry.val)
+    }
+}
@@ -2,6 +2,8 @@
 // Field modulus: p = 21888242871839275222246405745257275088696311157297823662689037894645226208583
 // = 0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47
 
+#![allow(clippy::needless_range_loop)] // limb arithmetic reads clearer with explicit indices
+
 // BN254 field modulus as u64 limbs (little-endian)
 pub const MODULUS: [u64; 4] = [
     0x3c208c16d87cfd47,
@@ -26,9 +28,98 @@ pub const R2: [u64; 4] = [
     0x06d89f71cab8351f,
 ];
 
+// Montgomery R^3 mod p, which turns a plain inverse of a * R back into
+// Montgomery form
+const R3: [u64; 4] = [
+    0xb1cd6dafda1530df,
+    0x62f210e6a7283db6,
+    0xef7f0b0c0ada0afb,
+    0x20fd6e902d592544,
+];
+
 // -p^{-1} mod 2^64
 const INV: u64 = 0x87d20782e4866389;
 
+/// Fq multiplication counter for cost comparisons in tests
+///
+/// Native test builds run the contract as plain Rust, so `env.budget()`
+/// only meters host calls and not the field arithmetic that dominates the
+/// cost of a wasm deployment. Fq multiplications and squarings are a
+/// deterministic proxy for that cost; they are counted separately since a
+/// squaring is cheaper. Inversions are counted on their own as well: the
+/// binary Euclidean algorithm uses no multiplications, but each one costs
+/// several hundred limb additions and shifts.
+#[cfg(test)]
+pub mod op_count {
+    use core::cell::Cell;
+
+    std::thread_local! {
+        static FQ_MULS: Cell<u64> = const { Cell::new(0) };
+        static FQ_SQUARES: Cell<u64> = const { Cell::new(0) };
+        static FQ_INVERSIONS: Cell<u64> = const { Cell::new(0) };
+    }
+
+    pub fn reset() {
+        FQ_MULS.with(|c| c.set(0));
+        FQ_SQUARES.with(|c| c.set(0));
+        FQ_INVERSIONS.with(|c| c.set(0));
+    }
+
+    pub fn fq_muls() -> u64 {
+        FQ_MULS.with(|c| c.get())
+    }
+
+    pub fn fq_squares() -> u64 {
+        FQ_SQUARES.with(|c| c.get())
+    }
+
+    pub fn fq_inversions() -> u64 {
+        FQ_INVERSIONS.with(|c| c.get())
+    }
+
+    pub(super) fn record_mul() {
+        FQ_MULS.with(|c| c.set(c.get() + 1));
+    }
+
+    pub(super) fn record_square() {
+        FQ_SQUARES.with(|c| c.set(c.get() + 1));
+    }
+
+    pub(super) fn record_inversion() {
+        FQ_INVERSIONS.with(|c| c.set(c.get() + 1));
+    }
+}
+
+/// a >> 1
+fn shr1(a: &mut [u64; 4]) {
+    for i in 0..3 {
+        a[i] = (a[i] >> 1) | (a[i + 1] << 63);
+    }
+    a[3] >>= 1;
+}
+
+/// a - b for a >= b
+fn sub_limbs(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut result = [0u64; 4];
+    let mut borrow = 0i128;
+    for i in 0..4 {
+        borrow = a[i] as i128 - b[i] as i128 - borrow;
+        result[i] = borrow as u64;
+        borrow = if borrow < 0 { 1 } else { 0 };
+    }
+    result
+}
+
+/// Compare two little-endian limb arrays: a < b
+pub fn limbs_less_than(a: &[u64; 4], b: &[u64; 4]) -> bool {
+    for i in (0..4).rev() {
+        if a[i] != b[i] {
+            return a[i] < b[i];
+        }
+    }
+    false
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Fq {
     pub limbs: [u64; 4],
@@ -41,7 +132,10 @@ impl Fq {
     }
 
     /// Create from raw bytes (big-endian)
-    pub fn from_bytes_be(bytes: &[u8; 32]) -> Self {
+    ///
+    /// Returns `None` unless the encoded integer is strictly less than p, so
+    /// every field element has exactly one accepted encoding.
+    pub fn from_bytes_be(bytes: &[u8; 32]) -> Option<Self> {
         let mut limbs = [0u64; 4];
         for i in 0..4 {
             let offset = i * 8;
@@ -56,12 +150,15 @@ impl Fq {
                 bytes[offset + 7],
             ]);
         }
+        if !limbs_less_than(&limbs, &MODULUS) {
+            return None;
+        }
         // Convert to Montgomery form
-        Self::from_montgomery(limbs).mul(&Self::from_montgomery(R2))
+        Some(Self::from_montgomery(limbs).mul(&Self::from_montgomery(R2)))
     }
 
     /// Convert to bytes (big-endian)
-    pub fn to_bytes_be(&self) -> [u8; 32] {
+    pub fn to_bytes_be(self) -> [u8; 32] {
         // Convert from Montgomery form
         let normal = self.mul(&Fq::from_montgomery([1, 0, 0, 0]));
         let mut bytes = [0u8; 32];
@@ -135,43 +232,111 @@ impl Fq {
         Fq::zero().sub(self)
     }
 
-    /// Montgomery multiplication
+    /// Montgomery multiplication (CIOS)
+    ///
+    /// The accumulator needs two limbs beyond the operand width: one for the
+    /// carry out of `self * other.limbs[i]` and one for the carry out of the
+    /// reduction step. Dropping either silently corrupts the product.
     pub fn mul(&self, other: &Fq) -> Fq {
-        let mut result = [0u64; 4];
+        #[cfg(test)]
+        op_count::record_mul();
+
+        let mut t = [0u64; 6];
 
         for i in 0..4 {
             let mut carry = 0u128;
 
-            // result += self * other.limbs[i]
+            // t += self * other.limbs[i]
             for j in 0..4 {
-                carry += result[j] as u128 + (self.limbs[j] as u128) * (other.limbs[i] as u128);
-                result[j] = carry as u64;
+                carry += t[j] as u128 + (self.limbs[j] as u128) * (other.limbs[i] as u128);
+                t[j] = carry as u64;
                 carry >>= 64;
             }
+            let acc = t[4] as u128 + carry;
+            t[4] = acc as u64;
+            t[5] = (acc >> 64) as u64;
 
-            // Montgomery reduction step
-            let k = result[0].wrapping_mul(INV);
-            carry = 0;
+            // Montgomery reduction step: t = (t + k * p) / 2^64
+            let k = t[0].wrapping_mul(INV);
+            let mut carry = (t[0] as u128 + (k as u128) * (MODULUS[0] as u128)) >> 64;
 
-            for j in 0..4 {
-                carry += result[j] as u128 + (k as u128) * (MODULUS[j] as u128);
-                if j > 0 {
-                    result[j - 1] = carry as u64;
-                }
+            for j in 1..4 {
+                carry += t[j] as u128 + (k as u128) * (MODULUS[j] as u128);
+                t[j - 1] = carry as u64;
                 carry >>= 64;
             }
-            result[3] = carry as u64;
+            let acc = t[4] as u128 + carry;
+            t[3] = acc as u64;
+            t[4] = t[5] + (acc >> 64) as u64;
         }
 
-        Self::sub_modulus(&result)
+        // p < 2^254, so the result is below 2p and t[4] is always zero here
+        Self::sub_modulus(&[t[0], t[1], t[2], t[3]])
     }
 
-    /// Square
+    /// Montgomery squaring
+    ///
+    /// Computes the 512-bit square with each cross product a_i * a_j (i < j)
+    /// formed once and doubled (10 limb products instead of 16), then
+    /// reduces it with four Montgomery steps.
     pub fn square(&self) -> Fq {
-        self.mul(self)
+        #[cfg(test)]
+        op_count::record_square();
+
+        let a = &self.limbs;
+        let mut t = [0u64; 8];
+
+        // Cross products a_i * a_j for i < j
+        for i in 0..3 {
+            let mut carry = 0u128;
+            for j in (i + 1)..4 {
+                carry += t[i + j] as u128 + (a[i] as u128) * (a[j] as u128);
+                t[i + j] = carry as u64;
+                carry >>= 64;
+            }
+            t[i + 4] = carry as u64;
+        }
+
+        // Double them
+        t[7] = t[6] >> 63;
+        for i in (1..7).rev() {
+            t[i] = (t[i] << 1) | (t[i - 1] >> 63);
+        }
+
+        // Add the squares a_i^2 on the diagonal
+        let mut carry = 0u128;
+        for i in 0..4 {
+            let sq = (a[i] as u128) * (a[i] as u128);
+            carry += t[2 * i] as u128 + (sq as u64) as u128;
+            t[2 * i] = carry as u64;
+            carry >>= 64;
+            carry += t[2 * i + 1] as u128 + (sq >> 64);
+            t[2 * i + 1] = carry as u64;
+            carry >>= 64;
+        }
+
+        // Montgomery reduction: t = t / 2^256 mod p, one limb at a time
+        for i in 0..4 {
+            let k = t[i].wrapping_mul(INV);
+            let mut carry = 0u128;
+            for j in 0..4 {
+                carry += t[i + j] as u128 + (k as u128) * (MODULUS[j] as u128);
+                t[i + j] = carry as u64;
+                carry >>= 64;
+            }
+            // a^2 + k * p < 2^512, so the carry dies out before t[7]
+            for limb in t.iter_mut().skip(i + 4) {
+                carry += *limb as u128;
+                *limb = carry as u64;
+                carry >>= 64;
+            }
+        }
+
+        Self::sub_modulus(&[t[4], t[5], t[6], t[7]])
     }
 
     /// Power
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn pow(&self, exp: &[u64; 4]) -> Fq {
         let mut result = Fq::one();
         let mut base = *self;
@@ -188,9 +353,59 @@ impl Fq {
         result
     }
 
+    /// Multiplicative inverse by the binary extended Euclidean algorithm
+    ///
+    /// Runs on the Montgomery representation x = a * R, keeping b * x = u and
+    /// c * x = v (mod p) while u and v shrink towards 1 using only shifts and
+    /// subtractions. That yields x^{-1} = a^{-1} * R^{-1}, and a Montgomery
+    /// multiplication by R^3 brings it back to a^{-1} * R. Far cheaper than
+    /// the ~254 squarings of `inverse_fermat`; the inputs are public, so the
+    /// data-dependent running time is not a concern.
+    pub fn inverse(&self) -> Option<Fq> {
+        if self.is_zero() {
+            return None;
+        }
+
+        #[cfg(test)]
+        op_count::record_inversion();
+
+        const ONE: [u64; 4] = [1, 0, 0, 0];
+        let mut u = self.limbs;
+        let mut v = MODULUS;
+        let mut b = Fq { limbs: ONE };
+        let mut c = Fq::zero();
+
+        // gcd(u, p) = 1, so one of u, v reaches 1
+        while u != ONE && v != ONE {
+            while u[0] & 1 == 0 {
+                shr1(&mut u);
+                b = b.half();
+            }
+            while v[0] & 1 == 0 {
+                shr1(&mut v);
+                c = c.half();
+            }
+
+            // Both odd: the difference is even and the loop continues
+            if limbs_less_than(&u, &v) {
+                v = sub_limbs(&v, &u);
+                c = c.sub(&b);
+            } else {
+                u = sub_limbs(&u, &v);
+                b = b.sub(&c);
+            }
+        }
+
+        let inv = if u == ONE { b } else { c };
+        Some(inv.mul(&Fq::from_montgomery(R3)))
+    }
+
     /// Multiplicative inverse using Fermat's little theorem
     /// a^{-1} = a^{p-2} mod p
-    pub fn inverse(&self) -> Option<Fq> {
+    ///
+    /// Reference for `inverse`.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn inverse_fermat(&self) -> Option<Fq> {
         if self.is_zero() {
             return None;
         }
@@ -206,6 +421,51 @@ impl Fq {
         Some(self.pow(&exp))
     }
 
+    /// Invert every element with a single field inversion (Montgomery's trick)
+    ///
+    /// Writes values[i]^{-1} to inverses[i]; zeros have no inverse and are
+    /// written as zero. Costs 3(n - 1) multiplications plus one inversion.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn batch_inverse(values: &[Fq], inverses: &mut [Fq]) {
+        assert_eq!(values.len(), inverses.len());
+
+        // inverses[i] = product of the non-zero values before i
+        let mut acc = Fq::one();
+        for (value, prefix) in values.iter().zip(inverses.iter_mut()) {
+            *prefix = acc;
+            if !value.is_zero() {
+                acc = acc.mul(value);
+            }
+        }
+
+        // acc^{-1} = product of all inverses; peel them off from the back
+        let mut acc_inv = acc.inverse().unwrap();
+        for (value, inv) in values.iter().zip(inverses.iter_mut()).rev() {
+            if value.is_zero() {
+                *inv = Fq::zero();
+                continue;
+            }
+            *inv = inv.mul(&acc_inv);
+            acc_inv = acc_inv.mul(value);
+        }
+    }
+
+    /// a / 2 mod p, on either representation
+    fn half(&self) -> Fq {
+        let mut limbs = self.limbs;
+        if limbs[0] & 1 == 1 {
+            // a + p is even and below 2^255
+            let mut carry = 0u128;
+            for i in 0..4 {
+                carry = carry + limbs[i] as u128 + MODULUS[i] as u128;
+                limbs[i] = carry as u64;
+                carry >>= 64;
+            }
+        }
+        shr1(&mut limbs);
+        Fq { limbs }
+    }
+
     /// Helper: subtract modulus if needed
     fn sub_modulus(limbs: &[u64; 4]) -> Fq {
         // Check if >= modulus
@@ -290,29 +550,35 @@ impl Fq2 {
 
     /// Multiplication in Fq2: (a0 + a1*u) * (b0 + b1*u) where u^2 = -1
     pub fn mul(&self, other: &Fq2) -> Fq2 {
-        // (a0 + a1*u)(b0 + b1*u) = a0*b0 + a0*b1*u + a1*b0*u + a1*b1*u^2
-        //                        = a0*b0 - a1*b1 + (a0*b1 + a1*b0)*u
+        // (a0 + a1*u)(b0 + b1*u) = a0*b0 - a1*b1 + (a0*b1 + a1*b0)*u, with
+        // Karatsuba: a0*b1 + a1*b0 = (a0 + a1)(b0 + b1) - a0*b0 - a1*b1
         let a0b0 = self.c0.mul(&other.c0);
         let a1b1 = self.c1.mul(&other.c1);
-        let a0b1 = self.c0.mul(&other.c1);
-        let a1b0 = self.c1.mul(&other.c0);
+        let cross = self.c0.add(&self.c1).mul(&other.c0.add(&other.c1));
 
         Fq2 {
             c0: a0b0.sub(&a1b1),
-            c1: a0b1.add(&a1b0),
+            c1: cross.sub(&a0b0).sub(&a1b1),
         }
     }
 
+    /// Complex squaring: two multiplications
     pub fn square(&self) -> Fq2 {
-        // (a0 + a1*u)^2 = a0^2 + 2*a0*a1*u + a1^2*u^2
-        //               = a0^2 - a1^2 + 2*a0*a1*u
-        let a0_sq = self.c0.square();
-        let a1_sq = self.c1.square();
-        let two_a0_a1 = self.c0.mul(&self.c1).add(&self.c0.mul(&self.c1));
+        // (a0 + a1*u)^2 = a0^2 - a1^2 + 2*a0*a1*u
+        //               = (a0 + a1)(a0 - a1) + 2*a0*a1*u
+        let a0a1 = self.c0.mul(&self.c1);
 
         Fq2 {
-            c0: a0_sq.sub(&a1_sq),
-            c1: two_a0_a1,
+            c0: self.c0.add(&self.c1).mul(&self.c0.sub(&self.c1)),
+            c1: a0a1.add(&a0a1),
+        }
+    }
+
+    /// Multiply both components by an element of Fq
+    pub fn mul_by_fq(&self, scalar: &Fq) -> Fq2 {
+        Fq2 {
+            c0: self.c0.mul(scalar),
+            c1: self.c1.mul(scalar),
         }
     }
 
@@ -335,7 +601,7 @@ impl Fq2 {
     /// For Fq2 = Fq[u] / (u^2 + 1), frobenius(a + bu) = a + b*conj(u)
     /// where conj(u) = -u, so (a + bu)^p = a - bu
     pub fn frobenius_map(&self, power: usize) -> Fq2 {
-        if power % 2 == 0 {
+        if power.is_multiple_of(2) {
             // Even powers: no change
             *self
         } else {
@@ -352,6 +618,25 @@ impl Fq2 {
 mod tests {
     use super::*;
 
+    // Deterministic xorshift64 so the "random" elements are reproducible
+    fn next_u64(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    fn random_fq(state: &mut u64) -> Fq {
+        // Clearing the top three bits keeps the value below p
+        let limbs = [
+            next_u64(state),
+            next_u64(state),
+            next_u64(state),
+            next_u64(state) >> 3,
+        ];
+        Fq::from_montgomery(limbs)
+    }
+
     // ========== Fq Tests ==========
 
     #[test]
@@ -388,6 +673,58 @@ mod tests {
         assert!(zero.inverse().is_none());
     }
 
+    #[test]
+    fn test_fq_inverse_op_count() {
+        // The binary Euclidean inversion only adds the multiplication by R^3
+        let a = Fq::from_montgomery([1, 2, 3, 4]);
+        op_count::reset();
+        let _ = a.inverse();
+        assert_eq!((op_count::fq_muls(), op_count::fq_squares()), (1, 0));
+        assert_eq!(op_count::fq_inversions(), 1);
+    }
+
+    #[test]
+    fn test_fq_inverse_matches_fermat() {
+        let mut state = 0x9e3779b97f4a7c15;
+        let p_minus_1 = Fq::from_montgomery([MODULUS[0] - 1, MODULUS[1], MODULUS[2], MODULUS[3]]);
+        let edge = [
+            Fq::one(),
+            Fq::from_montgomery([1, 0, 0, 0]),
+            Fq::from_montgomery([2, 0, 0, 0]),
+            p_minus_1,
+            Fq::from_montgomery([u64::MAX, u64::MAX, u64::MAX, MODULUS[3] - 1]),
+        ];
+
+        for a in edge
+            .into_iter()
+            .chain((0..32).map(|_| random_fq(&mut state)))
+        {
+            let inv = a.inverse().unwrap();
+            assert_eq!(a.mul(&inv), Fq::one(), "a = {:?}", a);
+            assert_eq!(Some(inv), a.inverse_fermat());
+        }
+    }
+
+    #[test]
+    fn test_fq_batch_inverse() {
+        let mut state = 0x2545f4914f6cdd1d;
+        let mut values = [Fq::zero(); 9];
+        for (i, value) in values.iter_mut().enumerate() {
+            // Zeros at the ends and in the middle
+            if i % 4 != 0 {
+                *value = random_fq(&mut state);
+            }
+        }
+
+        let mut inverses = [Fq::one(); 9];
+        Fq::batch_inverse(&values, &mut inverses);
+        for (value, inv) in values.iter().zip(inverses.iter()) {
+            assert_eq!(*inv, value.inverse().unwrap_or(Fq::zero()));
+        }
+
+        Fq::batch_inverse(&[], &mut []);
+    }
+
     #[test]
     fn test_fq_add_commutative() {
         // Property: a + b = b + a
@@ -479,12 +816,34 @@ mod tests {
         assert_eq!(a.square(), a.mul(&a));
     }
 
+    #[test]
+    fn test_fq_square_op_count() {
+        // A squaring is counted as such, not as a multiplication
+        let a = Fq::from_montgomery([1, 2, 3, 4]);
+        op_count::reset();
+        let _ = a.square();
+        assert_eq!((op_count::fq_muls(), op_count::fq_squares()), (0, 1));
+    }
+
+    #[test]
+    fn test_fq_square_full_width_limbs() {
+        // Values with every limb bit set exercise the doubled cross products
+        // and the carries of the reduction
+        let p_minus_1 = Fq::from_montgomery([MODULUS[0] - 1, MODULUS[1], MODULUS[2], MODULUS[3]]);
+        let near_max = Fq::from_montgomery([u64::MAX, u64::MAX, u64::MAX, MODULUS[3] - 1]);
+        let mixed = Fq::from_montgomery([u64::MAX, 0, u64::MAX, 1 << 61]);
+
+        for a in [Fq::zero(), Fq::one(), p_minus_1, near_max, mixed] {
+            assert_eq!(a.square(), a.mul(&a));
+        }
+    }
+
     #[test]
     fn test_fq_bytes_roundtrip() {
         // Property: from_bytes(to_bytes(a)) = a (for valid inputs)
         let a = Fq::from_montgomery([1, 2, 3, 4]);
         let bytes = a.to_bytes_be();
-        let b = Fq::from_bytes_be(&bytes);
+        let b = Fq::from_bytes_be(&bytes).unwrap();
 
         // Convert both to normal form for comparison
         let a_normal = a.mul(&Fq::from_montgomery([1, 0, 0, 0]));
@@ -493,6 +852,33 @@ mod tests {
         assert_eq!(a_normal, b_normal);
     }
 
+    /// p + delta as big-endian bytes (delta in -1..=1)
+    fn modulus_plus_bytes(delta: i8) -> [u8; 32] {
+        let mut limbs = MODULUS;
+        match delta {
+            -1 => limbs[0] -= 1,
+            1 => limbs[0] += 1,
+            _ => {}
+        }
+        let mut bytes = [0u8; 32];
+        for i in 0..4 {
+            bytes[i * 8..i * 8 + 8].copy_from_slice(&limbs[3 - i].to_be_bytes());
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_fq_from_bytes_rejects_non_canonical() {
+        // p - 1 is the largest canonical encoding
+        let max = Fq::from_bytes_be(&modulus_plus_bytes(-1)).unwrap();
+        assert_eq!(max, Fq::one().neg());
+
+        // p and p + 1 would otherwise alias 0 and 1
+        assert_eq!(Fq::from_bytes_be(&modulus_plus_bytes(0)), None);
+        assert_eq!(Fq::from_bytes_be(&modulus_plus_bytes(1)), None);
+        assert_eq!(Fq::from_bytes_be(&[0xff; 32]), None);
+    }
+
     // ========== Fq2 Tests ==========
 
     #[test]
@@ -512,6 +898,22 @@ mod tests {
         assert_eq!(a.add(&b), b.add(&a));
     }
 
+    #[test]
+    fn test_fq2_mul_karatsuba_op_count() {
+        // Karatsuba: three Fq multiplications instead of four
+        let a = Fq2::new(
+            Fq::from_montgomery([1, 2, 3, 4]),
+            Fq::from_montgomery([5, 6, 7, 8]),
+        );
+        let b = Fq2::new(
+            Fq::from_montgomery([9, 1, 2, 3]),
+            Fq::from_montgomery([4, 5, 6, 7]),
+        );
+        op_count::reset();
+        let _ = a.mul(&b);
+        assert_eq!((op_count::fq_muls(), op_count::fq_squares()), (3, 0));
+    }
+
     #[test]
     fn test_fq2_mul_commutative() {
         // Property: a * b = b * a
@@ -543,6 +945,22 @@ mod tests {
         assert!(diff_c1.is_zero() || !diff_c1.is_zero()); // At least doesn't panic
     }
 
+    #[test]
+    fn test_fq2_inverse_random() {
+        let mut state = 0x853c49e6748fea9b;
+        for _ in 0..16 {
+            let a = Fq2::new(random_fq(&mut state), random_fq(&mut state));
+            assert_eq!(a.mul(&a.inverse().unwrap()), Fq2::one());
+        }
+
+        // Purely real and purely imaginary elements
+        let a = random_fq(&mut state);
+        let real = Fq2::new(a, Fq::zero());
+        let imaginary = Fq2::new(Fq::zero(), a);
+        assert_eq!(real.mul(&real.inverse().unwrap()), Fq2::one());
+        assert_eq!(imaginary.mul(&imaginary.inverse().unwrap()), Fq2::one());
+    }
+
     #[test]
     fn test_fq2_frobenius_map_even() {
         // Property: frobenius(a, even_power) = a
@@ -569,4 +987,25 @@ mod tests {
         let a = Fq2::new(Fq::from_montgomery([1, 0, 0, 0]), Fq::from_montgomery([2, 0, 0, 0]));
         assert_eq!(a.square(), a.mul(&a));
     }
+
+    #[test]
+    fn test_fq2_karatsuba_matches_schoolbook() {
+        let a = Fq2::new(
+            Fq::from_montgomery([u64::MAX, 7, u64::MAX, 1 << 60]),
+            Fq::from_montgomery([3, u64::MAX, 5, MODULUS[3] - 1]),
+        );
+        let b = Fq2::new(
+            Fq::from_montgomery([11, 13, u64::MAX, 1 << 59]),
+            Fq::from_montgomery([u64::MAX, u64::MAX, 17, 19]),
+        );
+
+        // (a0*b0 - a1*b1) + (a0*b1 + a1*b0)*u
+        let schoolbook = Fq2::new(
+            a.c0.mul(&b.c0).sub(&a.c1.mul(&b.c1)),
+            a.c0.mul(&b.c1).add(&a.c1.mul(&b.c0)),
+        );
+        assert_eq!(a.mul(&b), schoolbook);
+        assert_eq!(a.square(), a.mul(&a));
+        assert_eq!(a.mul_by_fq(&b.c0), a.mul(&Fq2::new(b.c0, Fq::zero())));
+    }
 }
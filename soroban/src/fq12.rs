@@ -6,6 +6,382 @@
 
 use crate::field::{Fq, Fq2};
 
+// Frobenius coefficients (Montgomery form), with ξ = u + 9.
+// Generated from the BN254 parameters; see Fq6::frobenius_map and
+// Fq12::frobenius_map for how they are applied.
+
+// ξ^((p^k - 1) / 3) for k = 0..5 (the sequence has period 6)
+const FROBENIUS_COEFF_FQ6_C1: [Fq2; 6] = [
+    // power 0
+    Fq2::new(
+        Fq::from_montgomery([
+            0xd35d438dc58f0d9d,
+            0x0a78eb28f5c70b3d,
+            0x666ea36f7879462c,
+            0x0e0a77c19a07df2f,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 1
+    Fq2::new(
+        Fq::from_montgomery([
+            0xb5773b104563ab30,
+            0x347f91c8a9aa6454,
+            0x7a007127242e0991,
+            0x1956bcd8118214ec,
+        ]),
+        Fq::from_montgomery([
+            0x6e849f1ea0aa4757,
+            0xaa1c7b6d89f89141,
+            0xb6e713cdfae0ca3a,
+            0x26694fbb4e82ebc3,
+        ]),
+    ),
+    // power 2
+    Fq2::new(
+        Fq::from_montgomery([
+            0x3350c88e13e80b9c,
+            0x7dce557cdb5e56b9,
+            0x6001b4b8b615564a,
+            0x2682e617020217e0,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 3
+    Fq2::new(
+        Fq::from_montgomery([
+            0xc9af22f716ad6bad,
+            0xb311782a4aa662b2,
+            0x19eeaf64e248c7f4,
+            0x20273e77e3439f82,
+        ]),
+        Fq::from_montgomery([
+            0xacc02860f7ce93ac,
+            0x3933d5817ba76b4c,
+            0x69e6188b446c8467,
+            0x0a46036d4417cc55,
+        ]),
+    ),
+    // power 4
+    Fq2::new(
+        Fq::from_montgomery([
+            0x71930c11d782e155,
+            0xa6bb947cffbe3323,
+            0xaa303344d4741444,
+            0x2c3b3f0d26594943,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 5
+    Fq2::new(
+        Fq::from_montgomery([
+            0xf91aba2654e8e3b1,
+            0x4771cb2fdc92ce12,
+            0xdcb16ae0fc8bdf35,
+            0x274aa195cd9d8be4,
+        ]),
+        Fq::from_montgomery([
+            0x5cfc50ae18811f8b,
+            0x4bb28433cb43988c,
+            0x4fd35f13c3b56219,
+            0x301949bd2fc8883a,
+        ]),
+    ),
+];
+
+// ξ^((2p^k - 2) / 3) for k = 0..5 (the sequence has period 6)
+const FROBENIUS_COEFF_FQ6_C2: [Fq2; 6] = [
+    // power 0
+    Fq2::new(
+        Fq::from_montgomery([
+            0xd35d438dc58f0d9d,
+            0x0a78eb28f5c70b3d,
+            0x666ea36f7879462c,
+            0x0e0a77c19a07df2f,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 1
+    Fq2::new(
+        Fq::from_montgomery([
+            0x7361d77f843abe92,
+            0xa5bb2bd3273411fb,
+            0x9c941f314b3e2399,
+            0x15df9cddbb9fd3ec,
+        ]),
+        Fq::from_montgomery([
+            0x5dddfd154bd8c949,
+            0x62cb29a5a4445b60,
+            0x37bc870a0c7dd2b9,
+            0x24830a9d3171f0fd,
+        ]),
+    ),
+    // power 2
+    Fq2::new(
+        Fq::from_montgomery([
+            0x71930c11d782e155,
+            0xa6bb947cffbe3323,
+            0xaa303344d4741444,
+            0x2c3b3f0d26594943,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 3
+    Fq2::new(
+        Fq::from_montgomery([
+            0x448a93a57b6762df,
+            0xbfd62df528fdeadf,
+            0xd858f5d00e9bd47a,
+            0x06b03d4d3476ec58,
+        ]),
+        Fq::from_montgomery([
+            0x2b19daf4bcc936d1,
+            0xa1a54e7a56f4299f,
+            0xb533eee05adeaef1,
+            0x170c812b84dda0b2,
+        ]),
+    ),
+    // power 4
+    Fq2::new(
+        Fq::from_montgomery([
+            0x3350c88e13e80b9c,
+            0x7dce557cdb5e56b9,
+            0x6001b4b8b615564a,
+            0x2682e617020217e0,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 5
+    Fq2::new(
+        Fq::from_montgomery([
+            0x843420f1d8dadbd6,
+            0x31f010c9183fcdb2,
+            0x436330b527a76049,
+            0x13d47447f11adfe4,
+        ]),
+        Fq::from_montgomery([
+            0xef494023a857fa74,
+            0x2a925d02d5ab101a,
+            0x83b015829ba62f10,
+            0x2539111d0c13aea3,
+        ]),
+    ),
+];
+
+// ξ^((p^k - 1) / 6) for k = 0..11
+const FROBENIUS_COEFF_FQ12_C1: [Fq2; 12] = [
+    // power 0
+    Fq2::new(
+        Fq::from_montgomery([
+            0xd35d438dc58f0d9d,
+            0x0a78eb28f5c70b3d,
+            0x666ea36f7879462c,
+            0x0e0a77c19a07df2f,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 1
+    Fq2::new(
+        Fq::from_montgomery([
+            0xaf9ba69633144907,
+            0xca6b1d7387afb78a,
+            0x11bded5ef08a2087,
+            0x02f34d751a1f3a7c,
+        ]),
+        Fq::from_montgomery([
+            0xa222ae234c492d72,
+            0xd00f02a4565de15b,
+            0xdc2ff3a253dfc926,
+            0x10a75716b3899551,
+        ]),
+    ),
+    // power 2
+    Fq2::new(
+        Fq::from_montgomery([
+            0xca8d800500fa1bf2,
+            0xf0c5d61468b39769,
+            0x0e201271ad0d4418,
+            0x04290f65bad856e6,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 3
+    Fq2::new(
+        Fq::from_montgomery([
+            0x365316184e46d97d,
+            0x0af7129ed4c96d9f,
+            0x659da72fca1009b5,
+            0x08116d8983a20d23,
+        ]),
+        Fq::from_montgomery([
+            0xb1df4af7c39c1939,
+            0x3d9f02878a73bf7f,
+            0x9b2220928caf0ae0,
+            0x26684515eff054a6,
+        ]),
+    ),
+    // power 4
+    Fq2::new(
+        Fq::from_montgomery([
+            0x3350c88e13e80b9c,
+            0x7dce557cdb5e56b9,
+            0x6001b4b8b615564a,
+            0x2682e617020217e0,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 5
+    Fq2::new(
+        Fq::from_montgomery([
+            0x86b76f821b329076,
+            0x408bf52b4d19b614,
+            0x53dfb9d0d985e92d,
+            0x051e20146982d2a7,
+        ]),
+        Fq::from_montgomery([
+            0x0fbc9cd47752ebc7,
+            0x6d8fffe33415de24,
+            0xbef22cf038cf41b9,
+            0x15c0edff3c66bf54,
+        ]),
+    ),
+    // power 6
+    Fq2::new(
+        Fq::from_montgomery([
+            0x68c3488912edefaa,
+            0x8d087f6872aabf4f,
+            0x51e1a24709081231,
+            0x2259d6b14729c0fa,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 7
+    Fq2::new(
+        Fq::from_montgomery([
+            0x8c84e580a568b440,
+            0xcd164d1de0c21302,
+            0xa692585790f737d5,
+            0x2d7100fdc71265ad,
+        ]),
+        Fq::from_montgomery([
+            0x99fdddf38c33cfd5,
+            0xc77267ed1213e931,
+            0xdc2052142da18f36,
+            0x1fbcf75c2da80ad7,
+        ]),
+    ),
+    // power 8
+    Fq2::new(
+        Fq::from_montgomery([
+            0x71930c11d782e155,
+            0xa6bb947cffbe3323,
+            0xaa303344d4741444,
+            0x2c3b3f0d26594943,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 9
+    Fq2::new(
+        Fq::from_montgomery([
+            0x05cd75fe8a3623ca,
+            0x8c8a57f293a85cee,
+            0x52b29e86b7714ea8,
+            0x2852e0e95d8f9306,
+        ]),
+        Fq::from_montgomery([
+            0x8a41411f14e0e40e,
+            0x59e26809ddfe0b0d,
+            0x1d2e2523f4d24d7d,
+            0x09fc095cf1414b83,
+        ]),
+    ),
+    // power 10
+    Fq2::new(
+        Fq::from_montgomery([
+            0x08cfc388c494f1ab,
+            0x19b315148d1373d4,
+            0x584e90fdcb6c0213,
+            0x09e1685bdf2f8849,
+        ]),
+        Fq::from_montgomery([
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+            0x0000000000000000,
+        ]),
+    ),
+    // power 11
+    Fq2::new(
+        Fq::from_montgomery([
+            0xb5691c94bd4a6cd1,
+            0x56f575661b581478,
+            0x64708be5a7fb6f30,
+            0x2b462e5e77aecd82,
+        ]),
+        Fq::from_montgomery([
+            0x2c63ef42612a1180,
+            0x29f16aae345bec69,
+            0xf95e18c648b216a4,
+            0x1aa36073a4cae0d4,
+        ]),
+    ),
+];
+
 // Fq6 = Fq2[v] / (v^3 - ξ) where ξ = u + 9
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Fq6 {
@@ -100,14 +476,52 @@ impl Fq6 {
     /// Multiply by non-residue ξ = u + 9 in Fq2
     /// ξ * a = (a.c0 * 9 - a.c1, a.c0 + a.c1 * 9)
     fn mul_by_nonresidue(a: &Fq2) -> Fq2 {
-        let nine = Fq::from_montgomery([9, 0, 0, 0]);
+        // 9 * a computed as 8 * a + a with additions
+        let a2 = a.add(a);
+        let a4 = a2.add(&a2);
+        let a8 = a4.add(&a4);
+        let a9 = a8.add(a);
 
         // ξ = (9, 1) in Fq2 representation
         // ξ * a = (9*a.c0 - a.c1, a.c0 + 9*a.c1)
-        Fq2::new(
-            nine.mul(&a.c0).sub(&a.c1),
-            a.c0.add(&nine.mul(&a.c1))
-        )
+        Fq2::new(a9.c0.sub(&a.c1), a.c0.add(&a9.c1))
+    }
+
+    /// Multiply every coefficient by an Fq2 scalar
+    pub fn mul_by_fq2(&self, scalar: &Fq2) -> Fq6 {
+        Fq6 {
+            c0: self.c0.mul(scalar),
+            c1: self.c1.mul(scalar),
+            c2: self.c2.mul(scalar),
+        }
+    }
+
+    /// Multiply by the sparse element b0 + b1*v (five Fq2 multiplications
+    /// instead of six)
+    pub fn mul_by_01(&self, b0: &Fq2, b1: &Fq2) -> Fq6 {
+        let v0 = self.c0.mul(b0);
+        let v1 = self.c1.mul(b1);
+
+        // c0 = v0 + ξ * a2 * b1, with a2 * b1 = (a1 + a2) * b1 - v1
+        let t = self.c1.add(&self.c2).mul(b1).sub(&v1);
+        let c0 = v0.add(&Self::mul_by_nonresidue(&t));
+
+        // c1 = (a0 + a1)(b0 + b1) - v0 - v1
+        let c1 = self.c0.add(&self.c1).mul(&b0.add(b1)).sub(&v0).sub(&v1);
+
+        // c2 = a2 * b0 + v1 = (a0 + a2) * b0 - v0 + v1
+        let c2 = self.c0.add(&self.c2).mul(b0).sub(&v0).add(&v1);
+
+        Fq6 { c0, c1, c2 }
+    }
+
+    /// Multiply every coefficient by an element of Fq
+    pub fn mul_by_fq(&self, scalar: &Fq) -> Fq6 {
+        Fq6 {
+            c0: self.c0.mul_by_fq(scalar),
+            c1: self.c1.mul_by_fq(scalar),
+            c2: self.c2.mul_by_fq(scalar),
+        }
     }
 
     /// Squaring (more efficient than mul(self))
@@ -139,9 +553,8 @@ impl Fq6 {
         let c1 = Self::mul_by_nonresidue(&self.c2.square()).sub(&self.c0.mul(&self.c1));
         let c2 = self.c1.square().sub(&self.c0.mul(&self.c2));
 
-        let tmp = self.c2.mul(&c1);
-        let tmp = Self::mul_by_nonresidue(&tmp);
-        let tmp = tmp.add(&self.c1.mul(&c2));
+        // norm = a0*c0 + ξ(a2*c1 + a1*c2)
+        let tmp = self.c2.mul(&c1).add(&self.c1.mul(&c2));
         let tmp = Self::mul_by_nonresidue(&tmp);
         let tmp = tmp.add(&self.c0.mul(&c0));
 
@@ -154,34 +567,21 @@ impl Fq6 {
         })
     }
 
-    /// Frobenius endomorphism (raise to p-th power)
+    /// Frobenius endomorphism: raise to the p^power-th power
+    ///
+    /// (c0 + c1*v + c2*v^2)^(p^k) = c0^(p^k) + c1^(p^k) * v^(p^k) + c2^(p^k) * v^(2p^k)
+    /// with v^(p^k) = ξ^((p^k - 1) / 3) * v and v^(2p^k) = ξ^((2p^k - 2) / 3) * v^2.
     pub fn frobenius_map(&self, power: usize) -> Fq6 {
-        // Frobenius coefficients for Fq6
-        // These are precomputed constants for v^p, v^(2p), etc.
         Fq6 {
             c0: self.c0.frobenius_map(power),
-            c1: self.c1.frobenius_map(power).mul(&Self::frobenius_coeff_fq6_c1(power)),
-            c2: self.c2.frobenius_map(power).mul(&Self::frobenius_coeff_fq6_c2(power)),
-        }
-    }
-
-    fn frobenius_coeff_fq6_c1(power: usize) -> Fq2 {
-        // Precomputed Frobenius coefficients
-        // These would be computed once and stored as constants
-        match power % 6 {
-            0 => Fq2::one(),
-            1 => Fq2::new(
-                Fq::from_montgomery([0x0000000000000000, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000]),
-                Fq::from_montgomery([0x0000000000000001, 0x0000000000000000, 0x0000000000000000, 0x0000000000000000])
-            ),
-            _ => Fq2::one(), // Simplified for now
-        }
-    }
-
-    fn frobenius_coeff_fq6_c2(power: usize) -> Fq2 {
-        match power % 6 {
-            0 => Fq2::one(),
-            _ => Fq2::one(), // Simplified for now
+            c1: self
+                .c1
+                .frobenius_map(power)
+                .mul(&FROBENIUS_COEFF_FQ6_C1[power % 6]),
+            c2: self
+                .c2
+                .frobenius_map(power)
+                .mul(&FROBENIUS_COEFF_FQ6_C2[power % 6]),
         }
     }
 }
@@ -198,13 +598,6 @@ impl Fq12 {
         Fq12 { c0, c1 }
     }
 
-    pub const fn zero() -> Self {
-        Fq12 {
-            c0: Fq6::zero(),
-            c1: Fq6::zero(),
-        }
-    }
-
     pub const fn one() -> Self {
         Fq12 {
             c0: Fq6::one(),
@@ -221,6 +614,7 @@ impl Fq12 {
     }
 
     /// Addition in Fq12
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn add(&self, other: &Fq12) -> Fq12 {
         Fq12 {
             c0: self.c0.add(&other.c0),
@@ -229,6 +623,7 @@ impl Fq12 {
     }
 
     /// Subtraction in Fq12
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn sub(&self, other: &Fq12) -> Fq12 {
         Fq12 {
             c0: self.c0.sub(&other.c0),
@@ -236,14 +631,6 @@ impl Fq12 {
         }
     }
 
-    /// Negation
-    pub fn neg(&self) -> Fq12 {
-        Fq12 {
-            c0: self.c0.neg(),
-            c1: self.c1.neg(),
-        }
-    }
-
     /// Multiplication in Fq12
     /// (a0 + a1*w) * (b0 + b1*w) = (a0*b0 + v*a1*b1) + (a0*b1 + a1*b0)*w
     pub fn mul(&self, other: &Fq12) -> Fq12 {
@@ -259,6 +646,30 @@ impl Fq12 {
         Fq12 { c0, c1 }
     }
 
+    /// Multiply by the sparse element c0 + (c3 + c4*v)*w with c0 ∈ Fq
+    ///
+    /// This is the shape of a Miller-loop line on the D-type twist, whose
+    /// non-zero slots are c0.c0, c1.c0 and c1.c1. Karatsuba over Fq6 with
+    /// both sparse products: 6 Fq and 10 Fq2 multiplications, against 54
+    /// Fq multiplications for a dense `mul`.
+    pub fn mul_by_034(&self, c0: &Fq, c3: &Fq2, c4: &Fq2) -> Fq12 {
+        let a = self.c0.mul_by_fq(c0);
+        let b = self.c1.mul_by_01(c3, c4);
+
+        // (a0 + a1)(c0 + c3 + c4*v) - a - b
+        let c3_plus_c0 = Fq2::new(c3.c0.add(c0), c3.c1);
+        let c1 = self
+            .c0
+            .add(&self.c1)
+            .mul_by_01(&c3_plus_c0, c4)
+            .sub(&a)
+            .sub(&b);
+
+        let c0 = a.add(&Self::mul_by_nonresidue_fq12(&b));
+
+        Fq12 { c0, c1 }
+    }
+
     /// Multiply by non-residue v in Fq12 tower
     fn mul_by_nonresidue_fq12(a: &Fq6) -> Fq6 {
         // v * (a0, a1, a2) = (ξ*a2, a0, a1) where ξ = u + 9
@@ -279,6 +690,116 @@ impl Fq12 {
         Fq12 { c0, c1 }
     }
 
+    /// Conjugation: (a0 + a1*w) -> (a0 - a1*w)
+    ///
+    /// This is the p^6-power Frobenius. For elements of the cyclotomic
+    /// subgroup (norm 1, e.g. after the easy part of the final
+    /// exponentiation) it is also the inverse.
+    pub fn conjugate(&self) -> Fq12 {
+        Fq12 {
+            c0: self.c0,
+            c1: self.c1.neg(),
+        }
+    }
+
+    /// Squaring in the cyclotomic subgroup
+    ///
+    /// Granger-Scott, "Faster Squaring in the Cyclotomic Subgroup of Sixth
+    /// Degree Extensions". Viewing Fq12 as Fq4^3 with Fq4 = Fq2[w^3], each
+    /// Fq4 component is squared with two Fq2 multiplications. Only valid for
+    /// elements of the cyclotomic subgroup.
+    pub fn cyclotomic_square(&self) -> Fq12 {
+        let r0 = &self.c0.c0;
+        let r4 = &self.c0.c1;
+        let r3 = &self.c0.c2;
+        let r2 = &self.c1.c0;
+        let r1 = &self.c1.c1;
+        let r5 = &self.c1.c2;
+
+        // t0 + t1*y = (r0 + r1*y)^2
+        let tmp = r0.mul(r1);
+        let t0 = r0
+            .add(r1)
+            .mul(&Fq6::mul_by_nonresidue(r1).add(r0))
+            .sub(&tmp)
+            .sub(&Fq6::mul_by_nonresidue(&tmp));
+        let t1 = tmp.add(&tmp);
+
+        // t2 + t3*y = (r2 + r3*y)^2
+        let tmp = r2.mul(r3);
+        let t2 = r2
+            .add(r3)
+            .mul(&Fq6::mul_by_nonresidue(r3).add(r2))
+            .sub(&tmp)
+            .sub(&Fq6::mul_by_nonresidue(&tmp));
+        let t3 = tmp.add(&tmp);
+
+        // t4 + t5*y = (r4 + r5*y)^2
+        let tmp = r4.mul(r5);
+        let t4 = r4
+            .add(r5)
+            .mul(&Fq6::mul_by_nonresidue(r5).add(r4))
+            .sub(&tmp)
+            .sub(&Fq6::mul_by_nonresidue(&tmp));
+        let t5 = tmp.add(&tmp);
+
+        // z0 = 3*t0 - 2*r0, z1 = 3*t1 + 2*r1
+        let z0 = t0.sub(r0);
+        let z0 = z0.add(&z0).add(&t0);
+        let z1 = t1.add(r1);
+        let z1 = z1.add(&z1).add(&t1);
+
+        // z2 = 3*ξ*t5 + 2*r2, z3 = 3*t4 - 2*r3
+        let tmp = Fq6::mul_by_nonresidue(&t5);
+        let z2 = r2.add(&tmp);
+        let z2 = z2.add(&z2).add(&tmp);
+        let z3 = t4.sub(r3);
+        let z3 = z3.add(&z3).add(&t4);
+
+        // z4 = 3*t2 - 2*r4, z5 = 3*t3 + 2*r5
+        let z4 = t2.sub(r4);
+        let z4 = z4.add(&z4).add(&t2);
+        let z5 = r5.add(&t3);
+        let z5 = z5.add(&z5).add(&t3);
+
+        Fq12 {
+            c0: Fq6::new(z0, z4, z3),
+            c1: Fq6::new(z2, z1, z5),
+        }
+    }
+
+    /// Exponentiation in the cyclotomic subgroup (square-and-multiply, MSB first)
+    pub fn cyclotomic_exp(&self, exp: u64) -> Fq12 {
+        let mut result = Fq12::one();
+
+        for i in (0..64 - exp.leading_zeros()).rev() {
+            result = result.cyclotomic_square();
+            if (exp >> i) & 1 == 1 {
+                result = result.mul(self);
+            }
+        }
+
+        result
+    }
+
+    /// `cyclotomic_exp` for an exponent given as little-endian limbs
+    pub fn cyclotomic_pow(&self, exp: &[u64; 4]) -> Fq12 {
+        let mut result = Fq12::one();
+        let bits = match exp.iter().rposition(|&limb| limb != 0) {
+            Some(top) => 64 * top as u32 + 64 - exp[top].leading_zeros(),
+            None => 0,
+        };
+
+        for i in (0..bits).rev() {
+            result = result.cyclotomic_square();
+            if (exp[i as usize / 64] >> (i % 64)) & 1 == 1 {
+                result = result.mul(self);
+            }
+        }
+
+        result
+    }
+
     /// Multiplicative inverse
     pub fn inverse(&self) -> Option<Fq12> {
         if self.is_zero() {
@@ -299,23 +820,61 @@ impl Fq12 {
         })
     }
 
-    /// Frobenius endomorphism
+    /// Frobenius endomorphism: raise to the p^power-th power
+    ///
+    /// w^(p^k) = ξ^((p^k - 1) / 6) * w, so only the w coefficient is scaled.
     pub fn frobenius_map(&self, power: usize) -> Fq12 {
         Fq12 {
             c0: self.c0.frobenius_map(power),
-            c1: self.c1.frobenius_map(power).mul(&Self::frobenius_coeff_fq12_c1(power)),
+            c1: self
+                .c1
+                .frobenius_map(power)
+                .mul_by_fq2(&FROBENIUS_COEFF_FQ12_C1[power % 12]),
+        }
+    }
+
+    /// Serialize as 12 big-endian 32-byte coefficients in tower order
+    ///
+    /// c0.c0.c0, c0.c0.c1, c0.c1.c0, ..., c1.c2.c1: the Fq6 halves, then
+    /// their Fq2 coefficients, then the Fq2 components. This is the order
+    /// of the nested arrays in snarkjs's `vk_alphabeta_12`.
+    pub fn to_bytes_be(self) -> [u8; 384] {
+        let mut bytes = [0u8; 384];
+        for (chunk, coeff) in bytes.chunks_exact_mut(32).zip(self.coeffs().iter()) {
+            chunk.copy_from_slice(&coeff.to_bytes_be());
         }
+        bytes
     }
 
-    fn frobenius_coeff_fq12_c1(power: usize) -> Fq6 {
-        // Precomputed Frobenius coefficients for Fq12
-        match power % 12 {
-            0 => Fq6::one(),
-            _ => Fq6::one(), // Simplified for now, real constants needed for production
+    /// Deserialize from `to_bytes_be` order, rejecting coefficients >= p
+    pub fn from_bytes_be(bytes: &[u8; 384]) -> Option<Fq12> {
+        let mut coeffs = [Fq::zero(); 12];
+        for (coeff, chunk) in coeffs.iter_mut().zip(bytes.chunks_exact(32)) {
+            *coeff = Fq::from_bytes_be(chunk.try_into().unwrap())?;
         }
+
+        let fq2 = |i: usize| Fq2::new(coeffs[2 * i], coeffs[2 * i + 1]);
+        Some(Fq12::new(
+            Fq6::new(fq2(0), fq2(1), fq2(2)),
+            Fq6::new(fq2(3), fq2(4), fq2(5)),
+        ))
+    }
+
+    /// The 12 Fq coefficients in serialization order
+    fn coeffs(&self) -> [Fq; 12] {
+        let mut coeffs = [Fq::zero(); 12];
+        let fq2s = [
+            self.c0.c0, self.c0.c1, self.c0.c2, self.c1.c0, self.c1.c1, self.c1.c2,
+        ];
+        for (pair, fq2) in coeffs.chunks_exact_mut(2).zip(fq2s.iter()) {
+            pair[0] = fq2.c0;
+            pair[1] = fq2.c1;
+        }
+        coeffs
     }
 
     /// Exponentiation by squaring
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn pow(&self, exp: &[u64; 4]) -> Fq12 {
         let mut result = Fq12::one();
         let mut base = *self;
@@ -336,6 +895,38 @@ impl Fq12 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::field::{op_count, MODULUS};
+
+    // Deterministic xorshift64 so the "random" elements are reproducible
+    fn next_u64(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    fn random_fq(state: &mut u64) -> Fq {
+        // Clearing the top three bits keeps the value below p
+        let limbs = [
+            next_u64(state),
+            next_u64(state),
+            next_u64(state),
+            next_u64(state) >> 3,
+        ];
+        Fq::from_montgomery(limbs)
+    }
+
+    fn random_fq2(state: &mut u64) -> Fq2 {
+        Fq2::new(random_fq(state), random_fq(state))
+    }
+
+    fn random_fq6(state: &mut u64) -> Fq6 {
+        Fq6::new(random_fq2(state), random_fq2(state), random_fq2(state))
+    }
+
+    fn random_fq12(state: &mut u64) -> Fq12 {
+        Fq12::new(random_fq6(state), random_fq6(state))
+    }
 
     #[test]
     fn test_fq6_add() {
@@ -367,4 +958,203 @@ mod tests {
         let inv = a.inverse().unwrap();
         assert!(inv.is_one());
     }
+
+    #[test]
+    fn test_fq6_inverse_random() {
+        let mut state = 0x9e3779b97f4a7c15;
+        for _ in 0..8 {
+            let a = random_fq6(&mut state);
+            assert_eq!(a.mul(&a.inverse().unwrap()), Fq6::one());
+        }
+    }
+
+    #[test]
+    fn test_fq12_inverse_random() {
+        let mut state = 0x2545f4914f6cdd1d;
+        for _ in 0..8 {
+            let a = random_fq12(&mut state);
+            assert!(a.mul(&a.inverse().unwrap()).is_one());
+        }
+    }
+
+    #[test]
+    fn test_fq12_square_equals_mul_self() {
+        let mut state = 0x853c49e6748fea9b;
+        for _ in 0..8 {
+            let a = random_fq12(&mut state);
+            assert_eq!(a.square(), a.mul(&a));
+            assert_eq!(a.c0.square(), a.c0.mul(&a.c0));
+        }
+    }
+
+    #[test]
+    fn test_fq6_frobenius_map_matches_pow() {
+        // Fq6 embeds in Fq12 as c0, so Fq12::pow gives the reference a^(p^k)
+        let mut state = 0xda942042e4dd58b5;
+        for _ in 0..2 {
+            let a = random_fq6(&mut state);
+            let mut expected = Fq12::new(a, Fq6::zero());
+            for k in 1..12 {
+                expected = expected.pow(&MODULUS);
+                assert_eq!(a.frobenius_map(k), expected.c0, "power {}", k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_fq12_frobenius_map_matches_pow() {
+        let mut state = 0x6a09e667f3bcc908;
+        for _ in 0..2 {
+            let a = random_fq12(&mut state);
+            let mut expected = a;
+            for k in 1..12 {
+                expected = expected.pow(&MODULUS);
+                assert_eq!(a.frobenius_map(k), expected, "power {}", k);
+            }
+            // p^12 is the identity on Fq12
+            assert_eq!(expected.pow(&MODULUS), a);
+        }
+    }
+
+    #[test]
+    fn test_fq12_frobenius_map_composes() {
+        let mut state = 0xbb67ae8584caa73b;
+        let a = random_fq12(&mut state);
+        for j in 1..6 {
+            for k in 1..6 {
+                assert_eq!(a.frobenius_map(j).frobenius_map(k), a.frobenius_map(j + k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_fq12_cyclotomic_square_matches_square() {
+        let mut state = 0x3c6ef372fe94f82b;
+        for _ in 0..4 {
+            // Map into the cyclotomic subgroup with the easy part f^((p^6 - 1)(p^2 + 1))
+            let a = random_fq12(&mut state);
+            let t = a.conjugate().mul(&a.inverse().unwrap());
+            let c = t.frobenius_map(2).mul(&t);
+
+            assert_eq!(c.cyclotomic_square(), c.square());
+            assert!(c.mul(&c.conjugate()).is_one());
+        }
+    }
+
+    #[test]
+    fn test_fq12_cyclotomic_exp_matches_pow() {
+        let mut state = 0xa54ff53a5f1d36f1;
+        let a = random_fq12(&mut state);
+        let t = a.conjugate().mul(&a.inverse().unwrap());
+        let c = t.frobenius_map(2).mul(&t);
+
+        for exp in [0u64, 1, 2, 3, 0xff, 4965661367192848881, u64::MAX] {
+            assert_eq!(c.cyclotomic_exp(exp), c.pow(&[exp, 0, 0, 0]), "exp {}", exp);
+        }
+    }
+
+    #[test]
+    fn test_fq12_cyclotomic_pow_matches_pow() {
+        let mut state = 0x510e527fade682d1;
+        let a = random_fq12(&mut state);
+        let t = a.conjugate().mul(&a.inverse().unwrap());
+        let c = t.frobenius_map(2).mul(&t);
+
+        for exp in [
+            [0u64, 0, 0, 0],
+            [1, 0, 0, 0],
+            [u64::MAX, 1, 0, 0],
+            [
+                0x9b05688c2b3e6c1f,
+                0x1f83d9abfb41bd6b,
+                0x5be0cd19137e2179,
+                0x3c6ef372fe94f82b,
+            ],
+        ] {
+            assert_eq!(c.cyclotomic_pow(&exp), c.pow(&exp), "exp {:?}", exp);
+        }
+    }
+
+    #[test]
+    fn test_fq6_mul_by_01_matches_mul() {
+        let mut state = 0x5851f42d4c957f2d;
+        let a = random_fq6(&mut state);
+        let b0 = random_fq2(&mut state);
+        let b1 = random_fq2(&mut state);
+
+        assert_eq!(a.mul_by_01(&b0, &b1), a.mul(&Fq6::new(b0, b1, Fq2::zero())));
+    }
+
+    #[test]
+    fn test_fq12_op_counts() {
+        // Karatsuba Fq2 (3 muls) through Fq6 and Fq12, complex squaring, and
+        // sparse multiplication by a Miller-loop line
+        let mut state = 0x1f83d9abfb41bd6b;
+        let f = random_fq12(&mut state);
+        let g = random_fq12(&mut state);
+        let c0 = random_fq(&mut state);
+        let c3 = random_fq2(&mut state);
+        let c4 = random_fq2(&mut state);
+
+        op_count::reset();
+        let _ = f.mul(&g);
+        assert_eq!((op_count::fq_muls(), op_count::fq_squares()), (54, 0));
+
+        op_count::reset();
+        let _ = f.square();
+        assert_eq!((op_count::fq_muls(), op_count::fq_squares()), (36, 0));
+
+        op_count::reset();
+        let _ = f.mul_by_034(&c0, &c3, &c4);
+        assert_eq!((op_count::fq_muls(), op_count::fq_squares()), (36, 0));
+    }
+
+    #[test]
+    fn test_fq12_mul_by_034_matches_mul() {
+        let mut state = 0x2545f4914f6cdd1d;
+        for _ in 0..4 {
+            let f = random_fq12(&mut state);
+            let c0 = random_fq(&mut state);
+            let c3 = random_fq2(&mut state);
+            let c4 = random_fq2(&mut state);
+
+            let sparse = Fq12::new(
+                Fq6::new(Fq2::new(c0, Fq::zero()), Fq2::zero(), Fq2::zero()),
+                Fq6::new(c3, c4, Fq2::zero()),
+            );
+            assert_eq!(f.mul_by_034(&c0, &c3, &c4), f.mul(&sparse));
+        }
+    }
+
+    #[test]
+    fn test_fq12_bytes_roundtrip() {
+        let mut state = 0x0123456789abcdef;
+        for _ in 0..4 {
+            let a = random_fq12(&mut state);
+            assert_eq!(Fq12::from_bytes_be(&a.to_bytes_be()), Some(a));
+        }
+
+        // The first 32 bytes hold c0.c0.c0, the last 32 hold c1.c2.c1
+        let mut bytes = [0u8; 384];
+        bytes[31] = 1;
+        assert_eq!(Fq12::from_bytes_be(&bytes), Some(Fq12::one()));
+        bytes[31] = 0;
+        bytes[383] = 1;
+        let last = Fq12::from_bytes_be(&bytes).unwrap();
+        assert_eq!(last.c1.c2, Fq2::new(Fq::zero(), Fq::one()));
+    }
+
+    #[test]
+    fn test_fq12_from_bytes_rejects_non_canonical() {
+        let mut modulus = [0u8; 32];
+        for (i, limb) in MODULUS.iter().rev().enumerate() {
+            modulus[i * 8..i * 8 + 8].copy_from_slice(&limb.to_be_bytes());
+        }
+
+        for slot in 0..12 {
+            let mut bytes = Fq12::one().to_bytes_be();
+            bytes[slot * 32..slot * 32 + 32].copy_from_slice(&modulus);
+            assert_eq!(Fq12::from_bytes_be(&bytes), None, "slot {}", slot);
+        }
+    }
 }
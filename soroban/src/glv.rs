@@ -0,0 +1,204 @@
+// BN254 G1 GLV Scalar Decomposition
+// G1 has the endomorphism φ(x, y) = (β * x, y), with β a primitive cube
+// root of unity in Fq. On G1 it acts as multiplication by λ, a cube root of
+// unity mod r, so a scalar split as k = k1 + k2 * λ (mod r) with half-size
+// k1, k2 gives [k]P = [k1]P + [k2]φ(P) with half the doublings.
+//
+// The split uses the short basis of the lattice {(a, b) : a + b * λ = 0 mod r}
+// found by the extended Euclidean algorithm on (r, λ):
+//   v1 = (A1, -B1), v2 = (A2, B2), with A1 * B2 + A2 * B1 = r.
+// Writing (k, 0) in this basis and rounding the coefficients leaves a
+// remainder (k1, k2) in the fundamental domain, so |k1|, |k2| < 2^128.
+
+#![allow(clippy::needless_range_loop)] // limb arithmetic reads clearer with explicit indices
+
+// λ = 0x30644e72e131a029048b6e193fd84104cc37a73fec2bc5e9b8ca0b2d36636f23,
+// with λ^2 + λ + 1 = 0 mod r and [λ]P = φ(P) for the β of `G1Affine::endomorphism`
+#[cfg_attr(not(test), allow(dead_code))]
+pub const LAMBDA: [u64; 4] = [
+    0xb8ca0b2d36636f23,
+    0xcc37a73fec2bc5e9,
+    0x048b6e193fd84104,
+    0x30644e72e131a029,
+];
+
+// Short lattice basis
+const A1: u128 = 0x6f4d8248eeb859fc8211bbeb7d4f1128;
+const B1: u128 = 0x89d3256894d213e3;
+const A2: u128 = 0x89d3256894d213e3;
+const B2: u128 = 0x6f4d8248eeb859fd0be4e1541221250b;
+
+// round(2^256 * B2 / r) and round(2^256 * B1 / r), so that the basis
+// coefficients round(k * B2 / r) and round(k * B1 / r) are a multiplication
+// and a shift
+const G1: [u64; 4] = [0x5398fd0300ff6565, 0x4ccef014a773d2d2, 0x2, 0x0];
+const G2: [u64; 4] = [0xd91d232ec7e0b3d7, 0x2, 0x0, 0x0];
+
+/// Half-size scalar: (is negative, absolute value)
+pub type HalfScalar = (bool, u128);
+
+/// Split k < r into (k1, k2) with k = k1 + k2 * λ (mod r)
+///
+/// Both halves are below 2^128 in absolute value.
+pub fn decompose(k: &[u64; 4]) -> [HalfScalar; 2] {
+    let c1 = round_mul_shift(k, &G1);
+    let c2 = round_mul_shift(k, &G2);
+
+    // k1 = k - c1 * A1 - c2 * A2 and k2 = c1 * B1 - c2 * B2, computed mod
+    // 2^256 where the small results read as two's complement
+    let k1 = wrapping_sub(&wrapping_sub(k, &mul_u128(c1, A1)), &mul_u128(c2, A2));
+    let k2 = wrapping_sub(&mul_u128(c1, B1), &mul_u128(c2, B2));
+
+    [to_half_scalar(&k1), to_half_scalar(&k2)]
+}
+
+/// (a * b + 2^255) >> 256, for a result below 2^128
+fn round_mul_shift(a: &[u64; 4], b: &[u64; 4]) -> u128 {
+    let mut t = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            carry += t[i + j] as u128 + (a[i] as u128) * (b[j] as u128);
+            t[i + j] = carry as u64;
+            carry >>= 64;
+        }
+        t[i + 4] = carry as u64;
+    }
+
+    // Adding 2^255 can carry past limb 3 into the result
+    let (limb3, carry) = t[3].overflowing_add(1 << 63);
+    t[3] = limb3;
+    let mut carry = carry as u128;
+    for limb in t.iter_mut().skip(4) {
+        carry += *limb as u128;
+        *limb = carry as u64;
+        carry >>= 64;
+    }
+
+    (t[4] as u128) | ((t[5] as u128) << 64)
+}
+
+/// Full 256-bit product of two 128-bit values
+fn mul_u128(a: u128, b: u128) -> [u64; 4] {
+    let (a0, a1) = (a as u64 as u128, a >> 64);
+    let (b0, b1) = (b as u64 as u128, b >> 64);
+
+    let lo = a0 * b0;
+    let mid0 = a0 * b1;
+    let mid1 = a1 * b0;
+    let hi = a1 * b1;
+
+    // Sum the middle products into bits [64, 192) with their carries
+    let mut result = [lo as u64, 0, 0, 0];
+    let mut carry = (lo >> 64) + (mid0 as u64 as u128) + (mid1 as u64 as u128);
+    result[1] = carry as u64;
+    carry >>= 64;
+    carry += (mid0 >> 64) + (mid1 >> 64) + (hi as u64 as u128);
+    result[2] = carry as u64;
+    carry >>= 64;
+    result[3] = (carry + (hi >> 64)) as u64;
+    result
+}
+
+/// a - b mod 2^256
+fn wrapping_sub(a: &[u64; 4], b: &[u64; 4]) -> [u64; 4] {
+    let mut result = [0u64; 4];
+    let mut borrow = 0i128;
+    for i in 0..4 {
+        borrow = a[i] as i128 - b[i] as i128 - borrow;
+        result[i] = borrow as u64;
+        borrow = if borrow < 0 { 1 } else { 0 };
+    }
+    result
+}
+
+/// Sign and magnitude of a two's complement value in (-2^128, 2^128)
+fn to_half_scalar(a: &[u64; 4]) -> HalfScalar {
+    if a[3] >> 63 == 0 {
+        debug_assert!(a[2] == 0 && a[3] == 0);
+        (false, (a[0] as u128) | ((a[1] as u128) << 64))
+    } else {
+        let negated = wrapping_sub(&[0; 4], a);
+        debug_assert!(negated[2] == 0 && negated[3] == 0);
+        (true, (negated[0] as u128) | ((negated[1] as u128) << 64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::{Fr, MODULUS};
+
+    fn recompose(halves: &[HalfScalar; 2]) -> Fr {
+        let lambda = Fr::from_limbs(&LAMBDA).unwrap();
+        let [k1, k2] = halves.map(|(negative, magnitude)| {
+            let value =
+                Fr::from_limbs(&[magnitude as u64, (magnitude >> 64) as u64, 0, 0]).unwrap();
+            if negative {
+                value.neg()
+            } else {
+                value
+            }
+        });
+        k1.add(&k2.mul(&lambda))
+    }
+
+    #[test]
+    fn test_lambda_is_cube_root_of_unity() {
+        let lambda = Fr::from_limbs(&LAMBDA).unwrap();
+        assert_eq!(lambda.square().add(&lambda).add(&Fr::one()), Fr::zero());
+    }
+
+    #[test]
+    fn test_basis_determinant_is_r() {
+        // A1 * B2 + A2 * B1 = r
+        let a1b2 = mul_u128(A1, B2);
+        let a2b1 = mul_u128(A2, B1);
+        let neg_a2b1 = wrapping_sub(&[0; 4], &a2b1);
+        assert_eq!(wrapping_sub(&a1b2, &neg_a2b1), MODULUS);
+    }
+
+    #[test]
+    fn test_decompose_recomposes() {
+        let half_r = Fr::from_u64(2).inverse().unwrap().to_limbs();
+        let a1 = [A1 as u64, (A1 >> 64) as u64, 0, 0];
+        let b2 = [B2 as u64, (B2 >> 64) as u64, 0, 0];
+
+        // Edge scalars and the basis vectors, where the rounded coefficients
+        // change, each with its neighbours (0 - 1 wraps to r - 1)
+        let bases = [
+            [0; 4],
+            half_r,
+            LAMBDA,
+            a1,
+            b2,
+            [B1 as u64, 0, 0, 0],
+            [0, 0, 1, 0],
+        ];
+        let edges = bases.iter().flat_map(|base| {
+            let k = Fr::from_limbs(base).unwrap();
+            [k.sub(&Fr::one()), k, k.add(&Fr::one())]
+        });
+
+        let mut state = 0x9e3779b97f4a7c15u64;
+        let random = (0..64).map(|_| {
+            let limbs = core::array::from_fn(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state
+            });
+            Fr::reduce(&limbs)
+        });
+
+        for k in edges.chain(random) {
+            let halves = decompose(&k.to_limbs());
+            assert_eq!(recompose(&halves), k, "k = {:?}", k);
+            // Magnitudes fit in 128 bits by type; both are in fact below 2^127
+            assert!(halves.iter().all(|(_, magnitude)| magnitude >> 127 == 0));
+        }
+
+        assert_eq!(decompose(&[0; 4]), [(false, 0), (false, 0)]);
+        assert_eq!(decompose(&[1, 0, 0, 0]), [(false, 1), (false, 0)]);
+    }
+}
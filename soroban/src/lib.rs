@@ -1,17 +1,38 @@
 #![no_std]
 
-mod field;
+#[cfg(test)]
+extern crate std;
+
+mod access;
+mod batch;
+#[cfg(feature = "stage-bench")]
+mod bench;
 mod curve;
+pub mod events;
+mod field;
 mod fq12;
+mod glv;
+mod msm;
+mod nullifier;
 mod pairing;
+mod registry;
+mod scalar;
+mod validated;
 
 #[cfg(test)]
 mod tests;
 
-use soroban_sdk::{contract, contractimpl, contracttype, Bytes, Env, Vec};
-use field::{Fq, Fq2};
+pub use access::Role;
 use curve::{G1Affine, G2Affine};
-use pairing::pairing_check;
+use field::{Fq, Fq2};
+use fq12::Fq12;
+use pairing::{
+    multi_pairing_prepared, pairing, pairing_check, prepare_g2, LineCoeffs, MILLER_LINE_COUNT,
+};
+use scalar::Fr;
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, Address, Bytes, BytesN, Env, Vec,
+};
 
 // SPDX-License-Identifier: AGPL-3.0-or-later
 // -----------------------------------------------------------------------------
@@ -27,6 +48,10 @@ use pairing::pairing_check;
 // - L is computed from public inputs
 // -----------------------------------------------------------------------------
 
+/// G1 point as big-endian affine coordinates
+///
+/// The point at infinity is encoded as x = y = 0. (0, 0) is not on the
+/// curve, so the encoding cannot collide with a finite point.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct G1Point {
@@ -34,6 +59,9 @@ pub struct G1Point {
     pub y: Bytes,
 }
 
+/// G2 point as big-endian affine coordinates over Fq2, ordered [c0, c1]
+///
+/// The point at infinity is encoded with all four coordinates zero.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct G2Point {
@@ -59,6 +87,94 @@ pub struct VerifyingKey {
     pub ic: Vec<G1Point>, // IC[0] + IC[1] * public_input[0] + ... (precomputed)
 }
 
+/// Verifying key with e(α, β) and the Miller-loop lines of γ and δ
+/// precomputed
+///
+/// `alpha_beta` is e(α, β) serialized with `Fq12::to_bytes_be` (384
+/// bytes). This is the same value as snarkjs's `vk_alphabeta_12`, whose
+/// nested arrays flatten into that coefficient order.
+///
+/// Each `*_lines` field holds `MILLER_LINE_COUNT` lines in the order the
+/// Miller loop consumes them, every line as λ.c0 || λ.c1 || c.c0 || c.c1
+/// in 32-byte big-endian words (see `pairing::LineCoeffs`). Produced by
+/// `prepare_verifying_key`, which validates the key once so that
+/// `verify_proof_prepared` only has to check the proof.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreparedVerifyingKey {
+    pub alpha_beta: Bytes,
+    pub gamma_lines: Bytes,
+    pub delta_lines: Bytes,
+    pub ic: Vec<G1Point>,
+}
+
+/// Returned by `version`; bump with every change to the contract interface
+const CONTRACT_VERSION: u32 = 7;
+
+/// Public inputs whose IC points are decoded and combined per MSM call
+const MSM_BATCH: usize = 64;
+
+/// Most proofs a batch entrypoint takes in one call
+const MAX_BATCH: usize = 32;
+
+/// Encoded size of one precomputed line: four Fq elements
+const LINE_BYTES: u32 = 4 * 32;
+
+/// Encoded size of a GT element: twelve Fq elements
+const GT_BYTES: u32 = 12 * 32;
+
+/// Reason a call was rejected
+///
+/// The bool entrypoints (`verify_proof`, `verify_proof_prepared`, `verify`)
+/// only report success or failure; `verify_checked` returns the reason.
+/// Codes are part of the contract interface: append new variants, never
+/// renumber.
+#[contracterror]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VerifierError {
+    /// Proof elements have the wrong number of coordinates or bytes
+    MalformedProof = 1,
+    /// Verification key elements have the wrong shape or IC is empty
+    MalformedVerifyingKey = 2,
+    /// A coordinate or public input is not exactly 32 bytes
+    MalformedEncoding = 3,
+    /// Number of public inputs does not match vk.ic.len() - 1
+    PublicInputCountMismatch = 4,
+    /// A coordinate is not the canonical encoding of a value below p
+    NonCanonicalFieldElement = 5,
+    /// A public input is not the canonical encoding of a value below r
+    NonCanonicalScalar = 6,
+    /// A proof element or α, β, γ, δ is the point at infinity
+    IdentityPoint = 7,
+    /// A point is not on its curve
+    NotOnCurve = 8,
+    /// A point of the twist is not in the prime-order subgroup G2
+    NotInSubgroup = 9,
+    /// The Groth16 pairing equation does not hold
+    PairingCheckFailed = 10,
+    /// No verifying key is registered under the given ID
+    UnknownVerifyingKey = 11,
+    /// The registered verifying key was deprecated
+    DeprecatedVerifyingKey = 12,
+    /// `initialize` was already called
+    AlreadyInitialized = 13,
+    /// The contract has no admin yet
+    NotInitialized = 14,
+    /// The caller is neither the admin nor granted the required role
+    MissingRole = 15,
+    /// The proof's nullifier was already used with the verifying key
+    NullifierAlreadyUsed = 16,
+    /// The nullifier index is not that of a public input of the key
+    InvalidNullifierInput = 17,
+    /// The verifying key already has a nullifier input
+    NullifierInputAlreadySet = 18,
+    /// A batch has a different number of proofs and input vectors
+    BatchLengthMismatch = 19,
+    /// A batch has more than `MAX_BATCH` proofs
+    BatchTooLarge = 20,
+}
+
 #[contract]
 pub struct Groth16Verifier;
 
@@ -76,27 +192,37 @@ impl Groth16Verifier {
         vk: VerifyingKey,
         public_inputs: Vec<Bytes>,
     ) -> bool {
-        // 1. Validate inputs
-        if !Self::validate_proof_structure(&env, &proof) {
-            return false;
-        }
+        Self::verify_proof_checked(env, proof, vk, public_inputs).is_ok()
+    }
 
-        if !Self::validate_vk_structure(&env, &vk) {
-            return false;
-        }
+    /// `verify_proof`, failing with the first check that rejects the proof
+    ///
+    /// Fails with the decoding, validation or pairing error of the proof,
+    /// the verifying key or the public inputs.
+    pub fn verify_proof_checked(
+        env: Env,
+        proof: ProofData,
+        vk: VerifyingKey,
+        public_inputs: Vec<Bytes>,
+    ) -> Result<(), VerifierError> {
+        access::extend_instance_ttl(&env);
+        Self::check_proof(&env, &proof, &vk, &public_inputs)
+    }
 
-        // Check public inputs length matches vk.ic length - 1
-        if public_inputs.len() + 1 != vk.ic.len() {
-            return false;
-        }
+    /// Run every verification step, reporting the first one that fails
+    fn check_proof(
+        env: &Env,
+        proof: &ProofData,
+        vk: &VerifyingKey,
+        public_inputs: &Vec<Bytes>,
+    ) -> Result<(), VerifierError> {
+        // 1. Decode and validate the proof and key (see `validated`)
+        let proof = validated::Proof::decode(env, proof)?;
+        let key = validated::Key::decode(env, vk)?;
 
         // 2. Compute linear combination of IC points
         // L = IC[0] + IC[1] * public_input[0] + IC[2] * public_input[1] + ...
-        let vk_x = Self::compute_linear_combination(&env, &vk.ic, &public_inputs);
-        if vk_x.is_none() {
-            return false;
-        }
-        let vk_x = vk_x.unwrap();
+        let vk_x = validated::input_commitment(env, &vk.ic, public_inputs)?;
 
         // 3. Check pairing equation
         // e(A, B) = e(α, β) · e(L, γ) · e(C, δ)
@@ -107,45 +233,325 @@ impl Groth16Verifier {
         // Or using Miller loop + final exponentiation:
         // ML(A,B) · ML(-α,β) · ML(-L,γ) · ML(-C,δ) ^ final_exp = 1
 
-        Self::verify_pairing_equation(&env, &proof, &vk, &vk_x)
+        Self::verify_pairing_equation(&proof, &key, &vk_x)
+    }
+
+    /// Validate a verifying key and precompute e(α, β) and the lines of
+    /// γ and δ
+    ///
+    /// Runs every check `verify_proof` applies to the key, including the
+    /// curve check on each IC point, so the result can be stored and reused
+    /// with `verify_proof_prepared`. Can also be simulated off-chain.
+    /// Returns `None` if the key is rejected.
+    pub fn prepare_verifying_key(env: Env, vk: VerifyingKey) -> Option<PreparedVerifyingKey> {
+        access::extend_instance_ttl(&env);
+        Self::prepare_vk(&env, &vk).ok()
+    }
+
+    /// Verify a Groth16 proof against a prepared verifying key
+    ///
+    /// Checks e(A, B) · e(-L, γ) · e(-C, δ) = e(α, β) against the stored
+    /// e(α, β): three Miller loops instead of four, and those for γ and δ
+    /// only evaluate the stored lines at -L and -C.
+    pub fn verify_proof_prepared(
+        env: Env,
+        proof: ProofData,
+        pvk: PreparedVerifyingKey,
+        public_inputs: Vec<Bytes>,
+    ) -> bool {
+        access::extend_instance_ttl(&env);
+        Self::check_proof_prepared(&env, &proof, &pvk, &public_inputs).is_ok()
     }
 
-    /// Compute linear combination: IC[0] + Σ(IC[i+1] * public_input[i])
-    fn compute_linear_combination(
+    /// Validate `vk` and build its prepared form
+    fn prepare_vk(env: &Env, vk: &VerifyingKey) -> Result<PreparedVerifyingKey, VerifierError> {
+        let key = validated::Key::decode(env, vk)?;
+        for point in vk.ic.iter() {
+            validated::ic_point(env, &point)?;
+        }
+
+        let alpha_beta = pairing(&key.alpha, &key.beta);
+
+        // Points of G2 never hit a vertical line in the loop
+        let gamma_lines = prepare_g2(&key.gamma).ok_or(VerifierError::NotInSubgroup)?;
+        let delta_lines = prepare_g2(&key.delta).ok_or(VerifierError::NotInSubgroup)?;
+
+        Ok(PreparedVerifyingKey {
+            alpha_beta: Bytes::from_array(env, &alpha_beta.to_bytes_be()),
+            gamma_lines: Self::lines_to_bytes(env, &gamma_lines),
+            delta_lines: Self::lines_to_bytes(env, &delta_lines),
+            ic: vk.ic.clone(),
+        })
+    }
+
+    /// `check_proof` for a prepared verifying key
+    fn check_proof_prepared(
         env: &Env,
-        ic: &Vec<G1Point>,
+        proof: &ProofData,
+        pvk: &PreparedVerifyingKey,
         public_inputs: &Vec<Bytes>,
-    ) -> Option<G1Point> {
-        if ic.len() == 0 {
-            return None;
+    ) -> Result<(), VerifierError> {
+        // 1. Decode and validate the proof and key (see `validated`)
+        let proof = validated::Proof::decode(env, proof)?;
+        let key = validated::PreparedKey::decode(env, pvk)?;
+
+        // 2. Compute linear combination of IC points
+        let vk_x = validated::input_commitment(env, &pvk.ic, public_inputs)?;
+
+        // 3. e(A, B) · e(-L, γ) · e(-C, δ) = e(α, β), with only (A, B) run
+        // through the full Miller loop
+        let mut gamma_lines = key.gamma_lines.into_iter();
+        let mut delta_lines = key.delta_lines.into_iter();
+        let mut prepared: [pairing::PreparedPair; 2] = [
+            (vk_x.neg(), &mut gamma_lines),
+            (proof.c.neg(), &mut delta_lines),
+        ];
+
+        // Every stream holds exactly MILLER_LINE_COUNT lines after decoding
+        match multi_pairing_prepared(&[(proof.a, proof.b)], &mut prepared) {
+            Some(gt) if gt == key.alpha_beta => Ok(()),
+            Some(_) => Err(VerifierError::PairingCheckFailed),
+            None => Err(VerifierError::MalformedVerifyingKey),
+        }
+    }
+
+    /// Validate a verifying key and store it for use with `verify`
+    ///
+    /// Runs every check `verify_proof` applies to the key once, then stores
+    /// the key and its prepared form in persistent storage. Returns the
+    /// key's ID, the SHA-256 of its canonical encoding, which is the same
+    /// for every registration of the key. `caller` must be the admin or
+    /// hold `Role::Registrar`.
+    pub fn register_vk(
+        env: Env,
+        caller: Address,
+        vk: VerifyingKey,
+    ) -> Result<BytesN<32>, VerifierError> {
+        access::extend_instance_ttl(&env);
+        access::require_role(&env, &caller, Role::Registrar)?;
+        registry::register(&env, &vk)
+    }
+
+    /// Stop a registered verifying key from verifying proofs
+    ///
+    /// The key stays readable with `get_vk`. `caller` must be the admin or
+    /// hold `Role::Deprecator`.
+    pub fn deprecate_vk(env: Env, caller: Address, vk_id: BytesN<32>) -> Result<(), VerifierError> {
+        access::extend_instance_ttl(&env);
+        access::require_role(&env, &caller, Role::Deprecator)?;
+        registry::deprecate(&env, &vk_id)
+    }
+
+    /// Treat public input `input_index` of a registered key as a nullifier
+    ///
+    /// From then on, `verify_and_consume` accepts each value of that input
+    /// once, and every verification fails later proofs carrying it with
+    /// `NullifierAlreadyUsed`. Can only be set once per key. `caller` must
+    /// be the admin or hold `Role::Registrar`.
+    pub fn set_nullifier_input(
+        env: Env,
+        caller: Address,
+        vk_id: BytesN<32>,
+        input_index: u32,
+    ) -> Result<(), VerifierError> {
+        access::extend_instance_ttl(&env);
+        access::require_role(&env, &caller, Role::Registrar)?;
+        registry::set_nullifier_input(&env, &vk_id, input_index)
+    }
+
+    /// Index of the public input a registered key uses as a nullifier
+    pub fn nullifier_input(env: Env, vk_id: BytesN<32>) -> Option<u32> {
+        access::extend_instance_ttl(&env);
+        registry::nullifier_input(&env, &vk_id)
+    }
+
+    /// Whether a nullifier was already used with a registered key
+    pub fn is_nullifier_used(env: Env, vk_id: BytesN<32>, nullifier: BytesN<32>) -> bool {
+        access::extend_instance_ttl(&env);
+        nullifier::is_spent(&env, &vk_id, &nullifier)
+    }
+
+    /// Extend a used nullifier's entry to the maximum TTL; anyone may call
+    /// it. Returns false if the nullifier was not used.
+    pub fn extend_nullifier_ttl(env: Env, vk_id: BytesN<32>, nullifier: BytesN<32>) -> bool {
+        access::extend_instance_ttl(&env);
+        nullifier::extend_ttl(&env, &vk_id, &nullifier)
+    }
+
+    /// Whether a registered verifying key was deprecated
+    pub fn is_vk_deprecated(env: Env, vk_id: BytesN<32>) -> bool {
+        access::extend_instance_ttl(&env);
+        registry::is_deprecated(&env, &vk_id)
+    }
+
+    /// Get a registered verifying key by ID
+    pub fn get_vk(env: Env, vk_id: BytesN<32>) -> Option<VerifyingKey> {
+        access::extend_instance_ttl(&env);
+        registry::verifying_key(&env, &vk_id)
+    }
+
+    /// Verify a Groth16 proof against a registered verifying key
+    ///
+    /// Same check as `verify_proof_prepared` on the stored prepared key;
+    /// returns false if no key is registered under `vk_id` or it was
+    /// deprecated. `verify_checked` returns the reason instead.
+    ///
+    /// Only reads state: a proof whose nullifier was already used fails,
+    /// but a passing proof does not use its nullifier up (see
+    /// `verify_and_consume`).
+    pub fn verify(
+        env: Env,
+        vk_id: BytesN<32>,
+        proof: ProofData,
+        public_inputs: Vec<Bytes>,
+    ) -> bool {
+        access::extend_instance_ttl(&env);
+        Self::check_registered(&env, &vk_id, &proof, &public_inputs).is_ok()
+    }
+
+    /// `verify`, failing with the first check that rejects the proof
+    ///
+    /// Fails with `UnknownVerifyingKey` or `DeprecatedVerifyingKey` for the
+    /// key, or with the decoding, validation or pairing error of the proof
+    /// and public inputs.
+    pub fn verify_checked(
+        env: Env,
+        vk_id: BytesN<32>,
+        proof: ProofData,
+        public_inputs: Vec<Bytes>,
+    ) -> Result<(), VerifierError> {
+        access::extend_instance_ttl(&env);
+        Self::check_registered(&env, &vk_id, &proof, &public_inputs).map(|_| ())
+    }
+
+    /// `verify_checked`, then record the proof's nullifier as used
+    ///
+    /// The only entrypoint that spends nullifiers. `consumer` must authorize
+    /// the call and be the admin or hold `Role::Consumer`, so a third party
+    /// cannot use up a nullifier by submitting someone else's proof first.
+    /// For a key without a nullifier input, this is `verify_checked`.
+    pub fn verify_and_consume(
+        env: Env,
+        consumer: Address,
+        vk_id: BytesN<32>,
+        proof: ProofData,
+        public_inputs: Vec<Bytes>,
+    ) -> Result<(), VerifierError> {
+        access::extend_instance_ttl(&env);
+        access::require_role(&env, &consumer, Role::Consumer)?;
+        if let Some(nullifier) = Self::check_registered(&env, &vk_id, &proof, &public_inputs)? {
+            nullifier::spend(&env, &vk_id, &nullifier);
         }
+        Ok(())
+    }
 
-        // Start with IC[0]
-        let mut result = ic.get(0).unwrap();
+    /// `verify` for many proofs at once (see `batch`)
+    ///
+    /// Checks `proofs[i]` against `public_inputs[i]` for up to `MAX_BATCH`
+    /// proofs with one random linear combination: n + 2 Miller loops and
+    /// one final exponentiation instead of 3n Miller loops (three per proof
+    /// on the prepared path) and n final exponentiations. If the
+    /// combination fails, every proof is checked on its own. Returns one
+    /// code per proof, 0 if it verified, otherwise its `VerifierError` code.
+    /// Fails as a whole with `UnknownVerifyingKey`,
+    /// `DeprecatedVerifyingKey`, `BatchLengthMismatch` or `BatchTooLarge`.
+    ///
+    /// Like `verify`, only reads state; `consume_batch` spends nullifiers.
+    pub fn verify_batch(
+        env: Env,
+        vk_id: BytesN<32>,
+        proofs: Vec<ProofData>,
+        public_inputs: Vec<Vec<Bytes>>,
+    ) -> Result<Vec<u32>, VerifierError> {
+        access::extend_instance_ttl(&env);
+        batch::verify_batch(&env, &vk_id, &proofs, &public_inputs)
+    }
 
-        // Add IC[i+1] * public_input[i] for each public input
-        for i in 0..public_inputs.len() {
-            let scalar = public_inputs.get(i).unwrap();
-            let point = ic.get(i + 1).unwrap();
+    /// `verify_batch`, then record the nullifiers of the accepted proofs
+    ///
+    /// The batch counterpart of `verify_and_consume`: `consumer` must
+    /// authorize the call and be the admin or hold `Role::Consumer`.
+    /// Nullifiers are spent in batch order, so a value repeated within the
+    /// batch is accepted once.
+    pub fn consume_batch(
+        env: Env,
+        consumer: Address,
+        vk_id: BytesN<32>,
+        proofs: Vec<ProofData>,
+        public_inputs: Vec<Vec<Bytes>>,
+    ) -> Result<Vec<u32>, VerifierError> {
+        access::extend_instance_ttl(&env);
+        access::require_role(&env, &consumer, Role::Consumer)?;
+        batch::consume_batch(&env, &vk_id, &proofs, &public_inputs)
+    }
 
-            // Scalar multiplication: point * scalar
-            let scaled_point = Self::g1_scalar_mul(env, &point, &scalar)?;
+    /// `verify_batch` against a verifying key given with the call
+    ///
+    /// The key gets every check `verify_proof` applies and is prepared once
+    /// for the whole batch; a rejected key fails the call with its
+    /// `VerifierError`. Registering the key and calling `verify_batch`
+    /// saves preparing it on every call.
+    pub fn verify_proof_batch(
+        env: Env,
+        vk: VerifyingKey,
+        proofs: Vec<ProofData>,
+        public_inputs: Vec<Vec<Bytes>>,
+    ) -> Result<Vec<u32>, VerifierError> {
+        access::extend_instance_ttl(&env);
+        batch::verify_proof_batch(&env, &vk, &proofs, &public_inputs)
+    }
 
-            // Point addition: result = result + scaled_point
-            result = Self::g1_add(env, &result, &scaled_point)?;
+    /// `verify`, publishing the outcome as an event (see `events`)
+    ///
+    /// Returns false on rejection rather than failing, since a failed
+    /// invocation would discard the event; the event carries the
+    /// `VerifierError` code instead. `invoker`, when given, must authorize
+    /// the call and is recorded in the event.
+    pub fn verify_logged(
+        env: Env,
+        vk_id: BytesN<32>,
+        proof: ProofData,
+        public_inputs: Vec<Bytes>,
+        invoker: Option<Address>,
+    ) -> bool {
+        access::extend_instance_ttl(&env);
+        if let Some(invoker) = &invoker {
+            invoker.require_auth();
         }
 
-        Some(result)
+        let result = Self::check_registered(&env, &vk_id, &proof, &public_inputs);
+        let error_code = match result {
+            Ok(_) => 0,
+            Err(error) => error as u32,
+        };
+        let inputs_hash = events::inputs_hash(&env, &public_inputs);
+        events::publish_verification(&env, &vk_id, error_code, inputs_hash, invoker);
+
+        result.is_ok()
+    }
+
+    /// `check_proof` for a registered verifying key
+    ///
+    /// Returns the proof's nullifier, unused so far, if the key has one.
+    fn check_registered(
+        env: &Env,
+        vk_id: &BytesN<32>,
+        proof: &ProofData,
+        public_inputs: &Vec<Bytes>,
+    ) -> Result<Option<BytesN<32>>, VerifierError> {
+        let pvk = registry::prepared_key(env, vk_id)?;
+        let nullifier = nullifier::designated(env, vk_id, public_inputs)?;
+        Self::check_proof_prepared(env, proof, &pvk, public_inputs)?;
+        Ok(nullifier)
     }
 
     /// Verify the pairing equation using complete BN254 optimal ate pairing
     /// e(A, B) = e(α, β) · e(L, γ) · e(C, δ)
     fn verify_pairing_equation(
-        env: &Env,
-        proof: &ProofData,
-        vk: &VerifyingKey,
-        vk_x: &G1Point,
-    ) -> bool {
+        proof: &validated::Proof,
+        key: &validated::Key,
+        vk_x: &G1Affine,
+    ) -> Result<(), VerifierError> {
         // We check the pairing equation by computing:
         // e(A, B) · e(-α, β) · e(-L, γ) · e(-C, δ) = 1
         //
@@ -153,74 +559,35 @@ impl Groth16Verifier {
         // - A, B, C are proof elements (pi_a, pi_b, pi_c)
         // - α, β, γ, δ are from the verification key
         // - L is the linear combination of IC points (vk_x)
+        //
+        // Every point was validated when it was decoded, and L is a
+        // combination of points of G1.
+        let pairs = Self::groth16_pairs(proof, key, vk_x);
 
-        // Step 1: Validate all points are on their respective curves
-        if !Self::is_on_curve_g1(env, &proof.pi_a) {
-            return false;
-        }
-        if !Self::is_on_curve_g1(env, &proof.pi_c) {
-            return false;
-        }
-        if !Self::is_on_curve_g1(env, vk_x) {
-            return false;
-        }
-        if !Self::is_on_curve_g2(env, &proof.pi_b) {
-            return false;
+        // Perform the pairing check
+        // This uses the complete BN254 optimal ate pairing implementation
+        // with Miller loop and final exponentiation
+        if pairing_check(&pairs) {
+            Ok(())
+        } else {
+            Err(VerifierError::PairingCheckFailed)
         }
+    }
 
-        // Step 2: Convert contract types to affine points
-        let pi_a = match Self::bytes_to_g1affine(env, &proof.pi_a) {
-            Some(p) => p,
-            None => return false,
-        };
-        let pi_b = match Self::bytes_to_g2affine(env, &proof.pi_b) {
-            Some(p) => p,
-            None => return false,
-        };
-        let pi_c = match Self::bytes_to_g1affine(env, &proof.pi_c) {
-            Some(p) => p,
-            None => return false,
-        };
-        let alpha = match Self::bytes_to_g1affine(env, &vk.alpha) {
-            Some(p) => p,
-            None => return false,
-        };
-        let beta = match Self::bytes_to_g2affine(env, &vk.beta) {
-            Some(p) => p,
-            None => return false,
-        };
-        let gamma = match Self::bytes_to_g2affine(env, &vk.gamma) {
-            Some(p) => p,
-            None => return false,
-        };
-        let delta = match Self::bytes_to_g2affine(env, &vk.delta) {
-            Some(p) => p,
-            None => return false,
-        };
-        let vk_x_affine = match Self::bytes_to_g1affine(env, vk_x) {
-            Some(p) => p,
-            None => return false,
-        };
-
-        // Step 3: Negate G1 points as required by the equation
-        let neg_alpha = alpha.neg();
-        let neg_vk_x = vk_x_affine.neg();
-        let neg_pi_c = pi_c.neg();
-
-        // Step 4: Prepare pairs for multi-pairing check
-        // e(A, B) · e(-α, β) · e(-L, γ) · e(-C, δ) = 1
+    /// The four pairs of e(A, B) · e(-α, β) · e(-L, γ) · e(-C, δ)
+    fn groth16_pairs(
+        proof: &validated::Proof,
+        key: &validated::Key,
+        vk_x: &G1Affine,
+    ) -> [(G1Affine, G2Affine); 4] {
+        // Negate G1 points as required by the equation
         // Using an array since we're in no_std environment
-        let pairs = [
-            (pi_a, pi_b),         // e(A, B)
-            (neg_alpha, beta),    // e(-α, β)
-            (neg_vk_x, gamma),    // e(-L, γ)
-            (neg_pi_c, delta),    // e(-C, δ)
-        ];
-
-        // Step 5: Perform the pairing check
-        // This uses the complete BN254 optimal ate pairing implementation
-        // with Miller loop and final exponentiation
-        pairing_check(&pairs)
+        [
+            (proof.a, proof.b),          // e(A, B)
+            (key.alpha.neg(), key.beta), // e(-α, β)
+            (vk_x.neg(), key.gamma),     // e(-L, γ)
+            (proof.c.neg(), key.delta),  // e(-C, δ)
+        ]
     }
 
     /// Validate proof structure
@@ -274,7 +641,7 @@ impl Groth16Verifier {
         }
 
         // Validate IC points (G1)
-        if vk.ic.len() == 0 {
+        if vk.ic.is_empty() {
             return false;
         }
         for point in vk.ic.iter() {
@@ -286,212 +653,220 @@ impl Groth16Verifier {
         true
     }
 
-    /// Check if G1 point is on the curve (FULL IMPLEMENTATION)
-    /// BN254 curve equation: y² = x³ + 3
-    fn is_on_curve_g1(env: &Env, point: &G1Point) -> bool {
-        // For infinity point (0,0), return true
-        if Self::is_zero_bytes(&point.x) && Self::is_zero_bytes(&point.y) {
-            return true;
+    /// Validate prepared verification key structure
+    fn validate_prepared_vk_structure(_env: &Env, pvk: &PreparedVerifyingKey) -> bool {
+        if pvk.alpha_beta.len() != GT_BYTES {
+            return false;
         }
 
-        // Convert to affine and check
-        if let Some(affine) = Self::bytes_to_g1affine(env, point) {
-            affine.is_on_curve()
-        } else {
-            false
-        }
-    }
-
-    /// Check if G2 point is on the curve AND in the correct subgroup
-    ///
-    /// CRITICAL SECURITY: This function performs TWO checks:
-    /// 1. Point is on the curve equation: y² = x³ + b'
-    /// 2. Point is in the prime-order subgroup (cofactor check)
-    ///
-    /// Without the subgroup check, an attacker could provide points
-    /// from a different subgroup, breaking Groth16 soundness.
-    fn is_on_curve_g2(_env: &Env, point: &G2Point) -> bool {
-        // For infinity point, return true
-        if Self::is_zero_bytes(&point.x.get(0).unwrap())
-            && Self::is_zero_bytes(&point.x.get(1).unwrap())
-            && Self::is_zero_bytes(&point.y.get(0).unwrap())
-            && Self::is_zero_bytes(&point.y.get(1).unwrap())
-        {
-            return true;
+        let lines_len = MILLER_LINE_COUNT as u32 * LINE_BYTES;
+        for lines in [&pvk.gamma_lines, &pvk.delta_lines] {
+            if lines.len() != lines_len {
+                return false;
+            }
         }
 
-        // Convert to affine point for proper validation
-        let affine = match Self::bytes_to_g2affine(_env, point) {
-            Some(p) => p,
-            None => return false,
-        };
-
-        // Check 1: Point is on the curve
-        if !affine.is_on_curve() {
+        if pvk.ic.is_empty() {
             return false;
         }
+        for point in pvk.ic.iter() {
+            if point.x.len() != 32 || point.y.len() != 32 {
+                return false;
+            }
+        }
 
-        // Check 2: Point is in the correct subgroup (CRITICAL)
-        // This prevents subgroup attacks on Groth16
-        affine.is_in_correct_subgroup()
+        true
     }
 
-    /// G1 point addition (FULL IMPLEMENTATION)
-    fn g1_add(env: &Env, a: &G1Point, b: &G1Point) -> Option<G1Point> {
-        // Convert to affine points
-        let a_affine = Self::bytes_to_g1affine(env, a)?;
-        let b_affine = Self::bytes_to_g1affine(env, b)?;
+    /// Helper: Convert Bytes to G1Affine
+    fn bytes_to_g1affine(_env: &Env, point: &G1Point) -> Result<G1Affine, VerifierError> {
+        let x = Self::bytes_to_fq(&point.x)?;
+        let y = Self::bytes_to_fq(&point.y)?;
 
-        // Perform addition
-        let result_affine = a_affine.add(&b_affine);
+        if x.is_zero() && y.is_zero() {
+            return Ok(G1Affine::infinity());
+        }
 
-        // Convert back to bytes
-        Some(Self::g1affine_to_bytes(env, &result_affine))
+        Ok(G1Affine::new(x, y))
     }
 
-    /// G1 scalar multiplication (FULL IMPLEMENTATION)
-    fn g1_scalar_mul(env: &Env, point: &G1Point, scalar: &Bytes) -> Option<G1Point> {
-        // Convert point to affine
-        let point_affine = Self::bytes_to_g1affine(env, point)?;
-
-        // Convert scalar to u64 array
-        let scalar_array = Self::bytes_to_scalar(scalar)?;
-
-        // Perform scalar multiplication
-        let result_affine = point_affine.mul(&scalar_array);
+    /// Helper: Convert Bytes to G2Affine
+    fn bytes_to_g2affine(_env: &Env, point: &G2Point) -> Result<G2Affine, VerifierError> {
+        if point.x.len() != 2 || point.y.len() != 2 {
+            return Err(VerifierError::MalformedEncoding);
+        }
 
-        // Convert back to bytes
-        Some(Self::g1affine_to_bytes(env, &result_affine))
-    }
+        let x0 = Self::bytes_to_fq(&point.x.get(0).unwrap())?;
+        let x1 = Self::bytes_to_fq(&point.x.get(1).unwrap())?;
+        let y0 = Self::bytes_to_fq(&point.y.get(0).unwrap())?;
+        let y1 = Self::bytes_to_fq(&point.y.get(1).unwrap())?;
 
-    /// Negate a G1 point (FULL IMPLEMENTATION)
-    fn g1_negate(env: &Env, point: &G1Point) -> G1Point {
-        // Convert to affine
-        if let Some(affine) = Self::bytes_to_g1affine(env, point) {
-            // Negate
-            let neg_affine = affine.neg();
-            // Convert back
-            Self::g1affine_to_bytes(env, &neg_affine)
-        } else {
-            point.clone()
-        }
-    }
+        let x = Fq2::new(x0, x1);
+        let y = Fq2::new(y0, y1);
 
-    /// Helper: Convert Bytes to G1Affine
-    fn bytes_to_g1affine(_env: &Env, point: &G1Point) -> Option<G1Affine> {
-        if point.x.len() != 32 || point.y.len() != 32 {
-            return None;
+        if x.is_zero() && y.is_zero() {
+            return Ok(G2Affine::infinity());
         }
 
-        let mut x_bytes = [0u8; 32];
-        let mut y_bytes = [0u8; 32];
+        Ok(G2Affine::new(x, y))
+    }
 
-        for i in 0u32..32u32 {
-            x_bytes[i as usize] = point.x.get(i)?;
-            y_bytes[i as usize] = point.y.get(i)?;
+    /// Helper: Decode a GT element, rejecting coefficients >= p
+    fn bytes_to_fq12(bytes: &Bytes) -> Result<Fq12, VerifierError> {
+        if bytes.len() != GT_BYTES {
+            return Err(VerifierError::MalformedEncoding);
         }
 
-        let x = Fq::from_bytes_be(&x_bytes);
-        let y = Fq::from_bytes_be(&y_bytes);
+        let mut buf = [0u8; GT_BYTES as usize];
+        bytes.copy_into_slice(&mut buf);
 
-        Some(G1Affine::new(x, y))
+        Fq12::from_bytes_be(&buf).ok_or(VerifierError::NonCanonicalFieldElement)
     }
 
-    /// Helper: Convert G1Affine to Bytes
-    fn g1affine_to_bytes(env: &Env, point: &G1Affine) -> G1Point {
-        let x_bytes = point.x.to_bytes_be();
-        let y_bytes = point.y.to_bytes_be();
-
-        G1Point {
-            x: Bytes::from_array(env, &x_bytes),
-            y: Bytes::from_array(env, &y_bytes),
+    /// Helper: Encode precomputed lines as consecutive 128-byte records
+    fn lines_to_bytes(env: &Env, lines: &[LineCoeffs]) -> Bytes {
+        let mut bytes = Bytes::new(env);
+        for line in lines {
+            for fq in [&line.lambda.c0, &line.lambda.c1, &line.c.c0, &line.c.c1] {
+                bytes.extend_from_array(&fq.to_bytes_be());
+            }
         }
+        bytes
     }
 
-    /// Helper: Convert Bytes to G2Affine
-    fn bytes_to_g2affine(_env: &Env, point: &G2Point) -> Option<G2Affine> {
-        if point.x.len() != 2 || point.y.len() != 2 {
-            return None;
+    /// Helper: Decode the lines of one G2 point, rejecting values >= p
+    fn bytes_to_lines(bytes: &Bytes) -> Result<[LineCoeffs; MILLER_LINE_COUNT], VerifierError> {
+        if bytes.len() != MILLER_LINE_COUNT as u32 * LINE_BYTES {
+            return Err(VerifierError::MalformedVerifyingKey);
         }
 
-        let x0_bytes_raw = point.x.get(0)?;
-        let x1_bytes_raw = point.x.get(1)?;
-        let y0_bytes_raw = point.y.get(0)?;
-        let y1_bytes_raw = point.y.get(1)?;
-
-        if x0_bytes_raw.len() != 32
-            || x1_bytes_raw.len() != 32
-            || y0_bytes_raw.len() != 32
-            || y1_bytes_raw.len() != 32
-        {
-            return None;
+        let mut lines = [LineCoeffs::zero(); MILLER_LINE_COUNT];
+        for (i, line) in lines.iter_mut().enumerate() {
+            let start = i as u32 * LINE_BYTES;
+            let mut fq = [Fq::zero(); 4];
+            for (j, value) in fq.iter_mut().enumerate() {
+                let offset = start + j as u32 * 32;
+                *value = Self::bytes_to_fq(&bytes.slice(offset..offset + 32))?;
+            }
+            *line = LineCoeffs {
+                lambda: Fq2::new(fq[0], fq[1]),
+                c: Fq2::new(fq[2], fq[3]),
+            };
         }
 
-        let mut x0_bytes = [0u8; 32];
-        let mut x1_bytes = [0u8; 32];
-        let mut y0_bytes = [0u8; 32];
-        let mut y1_bytes = [0u8; 32];
+        Ok(lines)
+    }
 
-        for i in 0u32..32u32 {
-            x0_bytes[i as usize] = x0_bytes_raw.get(i)?;
-            x1_bytes[i as usize] = x1_bytes_raw.get(i)?;
-            y0_bytes[i as usize] = y0_bytes_raw.get(i)?;
-            y1_bytes[i as usize] = y1_bytes_raw.get(i)?;
+    /// Helper: Convert Bytes to a base field element, rejecting values >= p
+    fn bytes_to_fq(bytes: &Bytes) -> Result<Fq, VerifierError> {
+        if bytes.len() != 32 {
+            return Err(VerifierError::MalformedEncoding);
         }
 
-        let x0 = Fq::from_bytes_be(&x0_bytes);
-        let x1 = Fq::from_bytes_be(&x1_bytes);
-        let y0 = Fq::from_bytes_be(&y0_bytes);
-        let y1 = Fq::from_bytes_be(&y1_bytes);
+        let mut buf = [0u8; 32];
+        bytes.copy_into_slice(&mut buf);
 
-        let x = Fq2::new(x0, x1);
-        let y = Fq2::new(y0, y1);
-
-        Some(G2Affine::new(x, y))
+        Fq::from_bytes_be(&buf).ok_or(VerifierError::NonCanonicalFieldElement)
     }
 
-    /// Helper: Convert Bytes to scalar (u64 array)
-    fn bytes_to_scalar(bytes: &Bytes) -> Option<[u64; 4]> {
+    /// Helper: Convert big-endian Bytes to a scalar, rejecting values >= r
+    fn bytes_to_scalar(bytes: &Bytes) -> Result<Fr, VerifierError> {
         if bytes.len() != 32 {
-            return None;
+            return Err(VerifierError::MalformedEncoding);
         }
 
-        let mut scalar = [0u64; 4];
-        for i in 0..4 {
-            let offset = (i * 8) as u32;
-            let mut limb_bytes = [0u8; 8];
-            for j in 0u32..8u32 {
-                limb_bytes[j as usize] = bytes.get(offset + j)?;
-            }
-            scalar[i] = u64::from_be_bytes(limb_bytes);
-        }
+        let mut buf = [0u8; 32];
+        bytes.copy_into_slice(&mut buf);
 
-        Some(scalar)
+        Fr::from_bytes_be(&buf).ok_or(VerifierError::NonCanonicalScalar)
     }
 
-    /// Check if bytes are all zero
-    fn is_zero_bytes(bytes: &Bytes) -> bool {
-        for i in 0..bytes.len() {
-            if bytes.get(i).unwrap() != 0 {
-                return false;
-            }
-        }
-        true
+    /// Set the admin of a freshly deployed contract
+    ///
+    /// The admin has to authorize it. Fails if the contract already has an
+    /// admin. Until then anyone can claim the contract; see
+    /// `access::initialize` for deploying it safely.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), VerifierError> {
+        access::extend_instance_ttl(&env);
+        access::initialize(&env, &admin)
+    }
+
+    /// Get the admin, if the contract is initialized
+    pub fn admin(env: Env) -> Option<Address> {
+        access::extend_instance_ttl(&env);
+        access::admin(&env)
+    }
+
+    /// Hand the admin role over; both the current and the new admin
+    /// authorize it
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), VerifierError> {
+        access::extend_instance_ttl(&env);
+        access::set_admin(&env, &new_admin)
+    }
+
+    /// Grant `role` to `account` (admin only)
+    pub fn grant_role(env: Env, account: Address, role: Role) -> Result<(), VerifierError> {
+        access::extend_instance_ttl(&env);
+        access::grant_role(&env, &account, role)
+    }
+
+    /// Revoke `role` from `account` (admin only)
+    pub fn revoke_role(env: Env, account: Address, role: Role) -> Result<(), VerifierError> {
+        access::extend_instance_ttl(&env);
+        access::revoke_role(&env, &account, role)
+    }
+
+    /// Whether `account` is the admin or holds `role`
+    pub fn has_role(env: Env, account: Address, role: Role) -> bool {
+        access::extend_instance_ttl(&env);
+        access::has_role(&env, &account, role)
+    }
+
+    /// Replace the contract's code with already uploaded Wasm (admin only)
+    ///
+    /// Storage, including the admin and the registered keys, is kept.
+    /// The new code takes effect once this invocation completes.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), VerifierError> {
+        access::extend_instance_ttl(&env);
+        access::require_admin(&env)?;
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        Ok(())
     }
 
     /// Get verifier contract version
-    pub fn version(_env: Env) -> u32 {
-        5 // Version 5 with G2 subgroup validation (CRITICAL SECURITY FIX)
+    pub fn version(env: Env) -> u32 {
+        access::extend_instance_ttl(&env);
+        CONTRACT_VERSION
     }
 
     /// Get contract info
     pub fn info(env: Env) -> Vec<Bytes> {
+        access::extend_instance_ttl(&env);
         let mut info = Vec::new(&env);
         info.push_back(Bytes::from_slice(&env, b"OpenZKTool Groth16 Verifier"));
         info.push_back(Bytes::from_slice(&env, b"BN254 - Complete Pairing + Subgroup Check"));
-        info.push_back(Bytes::from_slice(&env, b"Version 5 - Production Ready"));
+        info.push_back(Self::version_label(&env));
         info
     }
+
+    /// "Version " followed by `CONTRACT_VERSION` in decimal
+    fn version_label(env: &Env) -> Bytes {
+        let mut digits = [0u8; 10];
+        let mut start = digits.len();
+        let mut value = CONTRACT_VERSION;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+
+        let mut label = Bytes::from_slice(env, b"Version ");
+        label.extend_from_slice(&digits[start..]);
+        label
+    }
 }
 
 #[cfg(test)]
@@ -525,7 +900,7 @@ mod test {
         let client = Groth16VerifierClient::new(&env, &contract_id);
 
         let version = client.version();
-        assert_eq!(version, 5); // Version 5 with G2 subgroup validation (CRITICAL SECURITY FIX)
+        assert_eq!(version, CONTRACT_VERSION);
     }
 
     #[test]
@@ -570,9 +945,6 @@ mod test {
             y: Bytes::from_array(&env, &[0u8; 32]),
         };
 
-        assert!(Groth16Verifier::is_on_curve_g1(&env, &infinity));
-
-        // Note: Real BN254 generator would need proper coordinates
-        // For now we test with infinity point which is always on curve
+        assert!(validated::ic_point(&env, &infinity).unwrap().is_infinity());
     }
 }
@@ -0,0 +1,345 @@
+// BN254 G1 Multi-Scalar Multiplication
+// Computes Σ [k_i] P_i over decoded points without any heap allocation.
+//
+// Both algorithms recode the scalars into signed windows with digits in
+// [-2^(c-1), 2^(c-1)], so negating a point (free in G1) halves the tables:
+// - Straus/Shamir interleaving (window 4) for few points: one shared
+//   doubling chain over GLV half-scalars, with an affine table of [1..8]P_i
+//   per point that also yields the multiples of φ(P_i).
+// - Pippenger's bucket method above `PIPPENGER_THRESHOLD`: per window, each
+//   point is added to the bucket of its digit and the buckets are summed.
+
+use crate::curve::{G1Affine, G1Projective};
+use crate::glv;
+
+/// Number of points from which Pippenger beats Straus, by Fq multiplication
+/// and squaring count (the two are within 1% at 56 points; Pippenger does
+/// not use the GLV split, so it only pulls ahead slowly after that)
+pub const PIPPENGER_THRESHOLD: usize = 56;
+
+/// Most points `straus` handles in one pass, bounding its table size
+const STRAUS_MAX_POINTS: usize = 32;
+
+/// Straus window width
+const STRAUS_WINDOW: usize = 4;
+
+/// Largest Pippenger window width, bounding the bucket array
+const PIPPENGER_MAX_WINDOW: usize = 8;
+
+/// Scalars are below r < 2^254
+const SCALAR_BITS: usize = 254;
+
+/// GLV halves of a scalar are below 2^128 in absolute value
+const HALF_SCALAR_BITS: usize = 128;
+
+/// Σ [scalars_i] points_i
+///
+/// Scalars are integers below r as little-endian limbs (as returned by
+/// `Fr::to_limbs`), so each is converted out of Montgomery form only once.
+/// Extra entries of the longer slice are ignored. The points must be in G1
+/// (on the curve), since the GLV split relies on φ(P) = [λ]P.
+pub fn g1_msm(points: &[G1Affine], scalars: &[[u64; 4]]) -> G1Projective {
+    let n = points.len().min(scalars.len());
+    if n < PIPPENGER_THRESHOLD {
+        straus(&points[..n], &scalars[..n])
+    } else {
+        pippenger(&points[..n], &scalars[..n])
+    }
+}
+
+/// Reference implementation: one double-and-add per point
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn g1_msm_naive(points: &[G1Affine], scalars: &[[u64; 4]]) -> G1Projective {
+    let mut acc = G1Projective::identity();
+    for (point, scalar) in points.iter().zip(scalars.iter()) {
+        acc = acc.add(&G1Projective::from_affine(point).mul_limbs(scalar));
+    }
+    acc
+}
+
+/// Number of signed digits of width c for a scalar below 2^bits; the extra
+/// digit absorbs the carry out of the top window
+const fn digit_count(bits: usize, c: usize) -> usize {
+    bits.div_ceil(c) + 1
+}
+
+/// Bits [i * c, (i + 1) * c) of the scalar
+fn window(limbs: &[u64; 4], c: usize, i: usize) -> u64 {
+    let bit = i * c;
+    if bit >= 256 {
+        return 0;
+    }
+
+    let limb = bit / 64;
+    let shift = bit % 64;
+    let mut window = limbs[limb] >> shift;
+    if shift + c > 64 && limb + 1 < 4 {
+        window |= limbs[limb + 1] << (64 - shift);
+    }
+    window & ((1 << c) - 1)
+}
+
+/// Signed digit k of width c, in [-2^(c-1), 2^(c-1)]
+///
+/// A window above 2^(c-1) becomes negative and carries 1 into the next
+/// one. A window of exactly 2^(c-1) only carries if it received a carry
+/// itself, so the carry into window k is found by scanning down past such
+/// windows, which almost always stops at k - 1.
+fn signed_digit(limbs: &[u64; 4], c: usize, k: usize) -> i32 {
+    let half = 1 << (c - 1);
+
+    let mut carry = 0;
+    for i in (0..k).rev() {
+        let below = window(limbs, c, i);
+        if below != half {
+            carry = (below > half) as u64;
+            break;
+        }
+    }
+
+    let value = window(limbs, c, k) + carry;
+    if value > half {
+        value as i32 - (1 << c)
+    } else {
+        value as i32
+    }
+}
+
+/// Straus/Shamir interleaving for up to `STRAUS_MAX_POINTS` points at a
+/// time; larger inputs are split into passes
+///
+/// Each scalar is split as k = k1 + k2 * λ (`glv::decompose`) and P's table
+/// also serves φ(P) = [λ]P, since φ([j]P) = [j]φ(P) costs one Fq
+/// multiplication. The shared doubling chain only covers the 128-bit halves.
+fn straus(points: &[G1Affine], scalars: &[[u64; 4]]) -> G1Projective {
+    const DIGITS: usize = digit_count(HALF_SCALAR_BITS, STRAUS_WINDOW);
+    const TABLE: usize = 1 << (STRAUS_WINDOW - 1);
+
+    let mut acc = G1Projective::identity();
+
+    for (points, scalars) in points
+        .chunks(STRAUS_MAX_POINTS)
+        .zip(scalars.chunks(STRAUS_MAX_POINTS))
+    {
+        // tables[i][j] = [j + 1] P_i, normalized to affine with one shared
+        // inversion so that every addition below is a mixed addition
+        let mut multiples = [G1Projective::identity(); TABLE * STRAUS_MAX_POINTS];
+        let mut tables = [[G1Affine::infinity(); TABLE]; STRAUS_MAX_POINTS];
+        // digits[i] = signed digits of (k1, k2) for P_i and φ(P_i)
+        let mut digits = [[[0i8; DIGITS]; 2]; STRAUS_MAX_POINTS];
+
+        for (i, (point, scalar)) in points.iter().zip(scalars.iter()).enumerate() {
+            let row = &mut multiples[i * TABLE..(i + 1) * TABLE];
+            row[0] = G1Projective::from_affine(point);
+            for j in 1..TABLE {
+                row[j] = row[j - 1].add_affine(point);
+            }
+
+            // A negative half negates its digits instead of its point
+            for (half, digits) in glv::decompose(scalar).iter().zip(digits[i].iter_mut()) {
+                let (negative, magnitude) = *half;
+                let limbs = [magnitude as u64, (magnitude >> 64) as u64, 0, 0];
+                for (k, digit) in digits.iter_mut().enumerate() {
+                    let value = signed_digit(&limbs, STRAUS_WINDOW, k) as i8;
+                    *digit = if negative { -value } else { value };
+                }
+            }
+        }
+        G1Projective::batch_to_affine(
+            &multiples[..points.len() * TABLE],
+            tables[..points.len()].as_flattened_mut(),
+        );
+
+        let mut pass = G1Projective::identity();
+        for k in (0..DIGITS).rev() {
+            for _ in 0..STRAUS_WINDOW {
+                pass = pass.double();
+            }
+            for i in 0..points.len() {
+                let [d1, d2] = [digits[i][0][k], digits[i][1][k]];
+                if d1 != 0 {
+                    let term = tables[i][d1.unsigned_abs() as usize - 1];
+                    pass = pass.add_affine(&if d1 > 0 { term } else { term.neg() });
+                }
+                if d2 != 0 {
+                    let term = tables[i][d2.unsigned_abs() as usize - 1].endomorphism();
+                    pass = pass.add_affine(&if d2 > 0 { term } else { term.neg() });
+                }
+            }
+        }
+
+        acc = acc.add(&pass);
+    }
+
+    acc
+}
+
+/// Pippenger window width for n points, roughly log2(n) - 2 clamped to
+/// [3, PIPPENGER_MAX_WINDOW]
+fn pippenger_window(n: usize) -> usize {
+    let log2 = usize::BITS as usize - n.leading_zeros() as usize;
+    log2.saturating_sub(2).clamp(3, PIPPENGER_MAX_WINDOW)
+}
+
+/// Pippenger's bucket method
+fn pippenger(points: &[G1Affine], scalars: &[[u64; 4]]) -> G1Projective {
+    let c = pippenger_window(points.len());
+    let buckets_len = 1 << (c - 1);
+
+    let mut acc = G1Projective::identity();
+    for k in (0..digit_count(SCALAR_BITS, c)).rev() {
+        for _ in 0..c {
+            acc = acc.double();
+        }
+
+        let mut buckets = [G1Projective::identity(); 1 << (PIPPENGER_MAX_WINDOW - 1)];
+        let buckets = &mut buckets[..buckets_len];
+
+        for (point, scalar) in points.iter().zip(scalars.iter()) {
+            let digit = signed_digit(scalar, c, k);
+            if digit > 0 {
+                let bucket = &mut buckets[digit as usize - 1];
+                *bucket = bucket.add_affine(point);
+            } else if digit < 0 {
+                let bucket = &mut buckets[(-digit) as usize - 1];
+                *bucket = bucket.add_affine(&point.neg());
+            }
+        }
+
+        // Σ j * bucket_j as a running sum from the top bucket down
+        let mut running = G1Projective::identity();
+        let mut window_sum = G1Projective::identity();
+        for bucket in buckets.iter().rev() {
+            running = running.add(bucket);
+            window_sum = window_sum.add(&running);
+        }
+
+        acc = acc.add(&window_sum);
+    }
+
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scalar::Fr;
+
+    // Deterministic xorshift64 so the "random" scalars are reproducible
+    fn next_u64(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    fn random_scalar(state: &mut u64) -> [u64; 4] {
+        // Clearing the top three bits keeps the value below r
+        [
+            next_u64(state),
+            next_u64(state),
+            next_u64(state),
+            next_u64(state) >> 3,
+        ]
+    }
+
+    fn random_inputs<const N: usize>(seed: u64) -> ([G1Affine; N], [[u64; 4]; N]) {
+        let mut state = seed;
+        let g = G1Affine::generator();
+        let points = core::array::from_fn(|i| g.mul(&Fr::from_u64(i as u64 * 7 + 3)));
+        let scalars = core::array::from_fn(|_| random_scalar(&mut state));
+        (points, scalars)
+    }
+
+    #[test]
+    fn test_signed_digits_recompose() {
+        // Σ d_k 2^(kc) must give back the scalar, with every digit in range
+        let mut state = 0x9e3779b97f4a7c15;
+        for c in [3, 4, 5, 8] {
+            for _ in 0..8 {
+                let scalar = random_scalar(&mut state);
+                let point = G1Affine::generator();
+
+                let mut acc = G1Projective::identity();
+                for k in (0..digit_count(SCALAR_BITS, c)).rev() {
+                    for _ in 0..c {
+                        acc = acc.double();
+                    }
+                    let digit = signed_digit(&scalar, c, k);
+                    assert!(digit.unsigned_abs() <= 1 << (c - 1));
+                    let term = point.mul_limbs(&[digit.unsigned_abs() as u64, 0, 0, 0]);
+                    let term = if digit < 0 { term.neg() } else { term };
+                    acc = acc.add_affine(&term);
+                }
+                assert_eq!(
+                    acc,
+                    G1Projective::from_affine(&point.mul_limbs(&scalar)),
+                    "c = {}",
+                    c
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_signed_digits_carry_through_half_windows() {
+        // A window of exactly 2^3 keeps its value without a carry in...
+        let digits: [i32; 5] = core::array::from_fn(|k| signed_digit(&[0x8888, 0, 0, 0], 4, k));
+        assert_eq!(digits, [8, 8, 8, 8, 0]);
+
+        // ...and passes a carry on: 0x888f = -1 + 16 * (-7 + 16 * (-7 + 16 * (-7 + 16)))
+        let digits: [i32; 5] = core::array::from_fn(|k| signed_digit(&[0x888f, 0, 0, 0], 4, k));
+        assert_eq!(digits, [-1, -7, -7, -7, 1]);
+    }
+
+    #[test]
+    fn test_straus_matches_naive() {
+        for n in [0, 1, 2, 5, 14] {
+            let (points, scalars) = random_inputs::<14>(n as u64 + 1);
+            assert_eq!(
+                straus(&points[..n], &scalars[..n]),
+                g1_msm_naive(&points[..n], &scalars[..n]),
+                "n = {}",
+                n
+            );
+        }
+
+        // More than one pass
+        let (points, scalars) = random_inputs::<{ STRAUS_MAX_POINTS + 3 }>(42);
+        assert_eq!(straus(&points, &scalars), g1_msm_naive(&points, &scalars));
+    }
+
+    #[test]
+    fn test_pippenger_matches_naive() {
+        let (points, scalars) = random_inputs::<70>(7);
+        for n in [1, 3, PIPPENGER_THRESHOLD, 70] {
+            assert_eq!(
+                pippenger(&points[..n], &scalars[..n]),
+                g1_msm_naive(&points[..n], &scalars[..n]),
+                "n = {}",
+                n
+            );
+        }
+    }
+
+    #[test]
+    fn test_msm_edge_cases() {
+        let g = G1Affine::generator();
+        let r_minus_1 = Fr::one().neg().to_limbs();
+        let points = [g, G1Affine::infinity(), g, g.neg(), g];
+        let scalars = [[0; 4], [5, 0, 0, 0], r_minus_1, [1, 0, 0, 0], [8, 0, 0, 0]];
+
+        // [0]G + [5]O + [r - 1]G + [1](-G) + [8]G = [6]G
+        let expected = G1Projective::from_affine(&g.mul(&Fr::from_u64(6)));
+        assert_eq!(g1_msm_naive(&points, &scalars), expected);
+        assert_eq!(straus(&points, &scalars), expected);
+        assert_eq!(pippenger(&points, &scalars), expected);
+
+        // Repeated points hit the doubling case in tables and buckets
+        let points = [g; 4];
+        let scalars = [[3, 0, 0, 0]; 4];
+        let expected = G1Projective::from_affine(&g.mul(&Fr::from_u64(12)));
+        assert_eq!(straus(&points, &scalars), expected);
+        assert_eq!(pippenger(&points, &scalars), expected);
+    }
+}
@@ -0,0 +1,80 @@
+// Nullifier Registry
+// A registered key can designate one public input as a nullifier (see
+// `registry::set_nullifier_input`). Proofs against such a key are accepted
+// once per value of that input: `verify_and_consume` (or `consume_batch`)
+// records the value under the key after a successful verification, and
+// later proofs carrying it fail with `NullifierAlreadyUsed`. Public inputs
+// are canonical (below r), so a value has a single encoding and cannot come
+// back as value + r.
+//
+// Only accounts holding `Role::Consumer` spend nullifiers, and they have to
+// authorize it. The other verification entrypoints only read the record:
+// otherwise anyone could copy a proof in flight and spend its nullifier
+// first, and the owner's own transaction would then fail for good.
+//
+// Spent nullifiers live in persistent storage. An entry whose TTL runs out
+// is archived, not deleted: transactions touching it fail until it is
+// restored, which brings the record back, so expiry never makes a nullifier
+// usable again. Entries get the maximum TTL when recorded, and anyone can
+// extend them with `extend_nullifier_ttl` to spare users the restore.
+
+use soroban_sdk::{contracttype, Bytes, BytesN, Env, Vec};
+
+use crate::{registry, VerifierError};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum NullifierKey {
+    /// Present once the nullifier was used with the key: (key ID, value)
+    Spent(BytesN<32>, BytesN<32>),
+}
+
+/// The nullifier among `public_inputs`, if the key with ID `vk_id` has one
+///
+/// Fails if it was already used, so a replay is rejected before the proof
+/// is verified.
+pub(crate) fn designated(
+    env: &Env,
+    vk_id: &BytesN<32>,
+    public_inputs: &Vec<Bytes>,
+) -> Result<Option<BytesN<32>>, VerifierError> {
+    let Some(index) = registry::nullifier_input(env, vk_id) else {
+        return Ok(None);
+    };
+    let input = public_inputs
+        .get(index)
+        .ok_or(VerifierError::PublicInputCountMismatch)?;
+    let nullifier = BytesN::<32>::try_from(input).map_err(|_| VerifierError::MalformedEncoding)?;
+
+    if is_spent(env, vk_id, &nullifier) {
+        return Err(VerifierError::NullifierAlreadyUsed);
+    }
+    Ok(Some(nullifier))
+}
+
+/// Record `nullifier` as used with the key with ID `vk_id`
+pub(crate) fn spend(env: &Env, vk_id: &BytesN<32>, nullifier: &BytesN<32>) {
+    let key = NullifierKey::Spent(vk_id.clone(), nullifier.clone());
+    env.storage().persistent().set(&key, &());
+    extend_ttl(env, vk_id, nullifier);
+}
+
+pub(crate) fn is_spent(env: &Env, vk_id: &BytesN<32>, nullifier: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .has(&NullifierKey::Spent(vk_id.clone(), nullifier.clone()))
+}
+
+/// Extend a spent nullifier's entry to the maximum TTL
+///
+/// Returns false, and does nothing, if the nullifier was not used.
+pub(crate) fn extend_ttl(env: &Env, vk_id: &BytesN<32>, nullifier: &BytesN<32>) -> bool {
+    let storage = env.storage().persistent();
+    let key = NullifierKey::Spent(vk_id.clone(), nullifier.clone());
+    if !storage.has(&key) {
+        return false;
+    }
+    let max_ttl = env.storage().max_ttl();
+    storage.extend_ttl(&key, max_ttl, max_ttl);
+    true
+}
@@ -16,12 +16,28 @@ use crate::fq12::{Fq6, Fq12};
 /// u = 4965661367192848881 (the BN parameter)
 const BN_U: u64 = 4965661367192848881;
 
-/// Miller loop parameter as bits (for double-and-add)
-/// This is 6*u + 2 for BN254
-/// The actual value is 29793968203157093288, which we split into multiple u64s
+/// Miller loop parameter: 6u + 2 = 29793968203157093288 for BN254
+/// This is a 65-bit value, split into u64 limbs (little-endian)
+#[cfg_attr(not(test), allow(dead_code))]
 const ATE_LOOP_COUNT: [u64; 2] = [
-    11829813394058045480, // lower 64 bits
-    1,                     // upper bits (value >> 64)
+    11347224129447541672, // lower 64 bits
+    1,                    // upper bits (value >> 64)
+];
+
+/// Non-adjacent form of 6u + 2, least significant digit first
+///
+/// sum(ATE_LOOP_COUNT_NAF[i] * 2^i) == 6u + 2, and no two adjacent digits
+/// are both non-zero, which leaves 22 non-zero digits against 35 set bits
+/// in plain binary. The top digit seeds the running point T = Q and the
+/// loop processes the remaining 65 from the top down. The digits fix the
+/// line schedule, and with it `MILLER_LINE_COUNT` and the lines stored in
+/// prepared keys.
+const ATE_LOOP_COUNT_NAF: [i8; 66] = [
+    0, 0, 0, 1, 0, 1, 0, -1, 0, 0, -1, 0, 0, 0, 1, 0, //
+    0, -1, 0, -1, 0, 0, 0, 1, 0, -1, 0, 0, 0, 0, -1, 0, //
+    0, 1, 0, -1, 0, 0, 1, 0, 0, 0, 0, 0, -1, 0, 0, -1, //
+    0, 1, 0, -1, 0, 0, 0, -1, 0, -1, 0, 0, 0, 1, 0, -1, //
+    0, 1,
 ];
 
 /// Compute the optimal ate pairing e(P, Q) where P ∈ G1 and Q ∈ G2
@@ -48,132 +64,347 @@ pub fn pairing(p: &G1Affine, q: &G2Affine) -> Fq12 {
 /// e(P1, Q1) * e(P2, Q2) * ... * e(Pn, Qn)
 ///
 /// This is more efficient than computing each pairing separately
-/// because we can do a single final exponentiation at the end.
+/// because the Miller loops share one accumulator and we do a single
+/// final exponentiation at the end.
 pub fn multi_pairing(pairs: &[(G1Affine, G2Affine)]) -> Fq12 {
     if pairs.is_empty() {
         return Fq12::one();
     }
 
-    // Compute Miller loop for all pairs
+    // Shared Miller loop over all pairs
+    let f = multi_miller_loop(pairs);
+
+    // Single final exponentiation
+    final_exponentiation(&f)
+}
+
+/// `multi_pairing` with some G2 points given by their precomputed lines
+///
+/// Returns `None` if a line stream is malformed (see
+/// `multi_miller_loop_prepared`).
+pub fn multi_pairing_prepared(
+    pairs: &[(G1Affine, G2Affine)],
+    prepared: &mut [PreparedPair],
+) -> Option<Fq12> {
+    let f = multi_miller_loop_prepared(pairs, prepared)?;
+    Some(final_exponentiation(&f))
+}
+
+/// Number of pairs advanced together by one shared Miller loop
+///
+/// The running G2 points live in a fixed-size array (no allocator in the
+/// contract). Longer inputs are processed in chunks of this size whose
+/// results are multiplied together.
+const MULTI_MILLER_WIDTH: usize = 8;
+
+/// Multi-Miller loop: Π f_{6u+2,Q_i}(P_i) with Frobenius corrections
+///
+/// All pairs are advanced together, so the Fq12 accumulator is squared
+/// once per loop digit instead of once per digit and pair. Pairs with a
+/// point at infinity contribute 1 and are skipped.
+pub fn multi_miller_loop(pairs: &[(G1Affine, G2Affine)]) -> Fq12 {
+    // Without prepared pairs there are no line streams that can run short
+    multi_miller_loop_prepared(pairs, &mut []).unwrap()
+}
+
+/// Number of line steps the Miller loop takes per G2 point: a doubling for
+/// each of the 65 lower digits of the NAF of 6u + 2, an addition for each of
+/// their 21 non-zero digits, and the two Frobenius correction steps
+pub const MILLER_LINE_COUNT: usize = 88;
+
+/// A G1 point with the precomputed Miller-loop lines of its G2 partner, in
+/// the order `prepare_g2` emits them
+pub type PreparedPair<'a> = (G1Affine, &'a mut dyn Iterator<Item = LineCoeffs>);
+
+/// Multi-Miller loop over regular pairs and pairs with precomputed lines
+///
+/// The prepared pairs share the accumulator with the regular ones; their
+/// lines are only evaluated at the G1 point. Returns `None` if a line
+/// stream does not hold exactly `MILLER_LINE_COUNT` lines.
+pub fn multi_miller_loop_prepared(
+    pairs: &[(G1Affine, G2Affine)],
+    prepared: &mut [PreparedPair],
+) -> Option<Fq12> {
     let mut f = Fq12::one();
+    let mut chunk = [(G1Affine::infinity(), G2Affine::infinity()); MULTI_MILLER_WIDTH];
+    let mut len = 0;
+
+    // The prepared pairs ride along with the first chunk
+    let mut prepared = Some(prepared);
+
     for (p, q) in pairs {
-        if !p.is_infinity() && !q.is_infinity() {
-            let fi = miller_loop(p, q);
-            f = f.mul(&fi);
+        if p.is_infinity() || q.is_infinity() {
+            continue;
+        }
+        chunk[len] = (*p, *q);
+        len += 1;
+
+        if len == MULTI_MILLER_WIDTH {
+            let prepared = prepared.take().unwrap_or(&mut []);
+            f = f.mul(&miller_loop_chunk(&chunk, prepared)?);
+            len = 0;
         }
     }
+    if len > 0 || prepared.as_ref().is_some_and(|p| !p.is_empty()) {
+        let prepared = prepared.take().unwrap_or(&mut []);
+        f = f.mul(&miller_loop_chunk(&chunk[..len], prepared)?);
+    }
 
-    // Single final exponentiation
-    final_exponentiation(&f)
+    Some(f)
 }
 
-/// Miller loop: the main accumulation phase of the pairing
+/// Miller loop for a single pair
+pub(crate) fn miller_loop(p: &G1Affine, q: &G2Affine) -> Fq12 {
+    multi_miller_loop(&[(*p, *q)])
+}
+
+/// Miller loop for at most `MULTI_MILLER_WIDTH` finite pairs
 ///
 /// Evaluates line functions along the Miller loop and accumulates them
-/// into an element of Fq12.
-fn miller_loop(p: &G1Affine, q: &G2Affine) -> Fq12 {
+/// into an element of Fq12. For the optimal ate pairing on BN curves each
+/// pair contributes
+///
+/// f_{6u+2,Q}(P) * l_{[6u+2]Q, π(Q)}(P) * l_{[6u+2]Q + π(Q), -π²(Q)}(P)
+///
+/// where π is the p-power Frobenius acting on the twist (`G2Affine::psi`).
+fn miller_loop_chunk(
+    pairs: &[(G1Affine, G2Affine)],
+    prepared: &mut [PreparedPair],
+) -> Option<Fq12> {
+    let mut running = [G2Affine::infinity(); MULTI_MILLER_WIDTH];
+    let running = &mut running[..pairs.len()];
+    let mut f = ate_loop(pairs, running, prepared)?;
+
+    // Frobenius correction steps
+    for ((p, q), r) in pairs.iter().zip(running.iter_mut()) {
+        let added;
+        (f, added) = add_step(&f, r, &q.psi(), p);
+        (f, _) = add_step(&f, &added, &q.psi2().neg(), p);
+    }
+    for _ in 0..2 {
+        f = mul_prepared_lines(f, prepared)?;
+    }
+
+    // Every stream must be used up exactly
+    for (_, lines) in prepared.iter_mut() {
+        if lines.next().is_some() {
+            return None;
+        }
+    }
+
+    Some(f)
+}
+
+/// The 6u + 2 part of the Miller loop, shared across pairs
+///
+/// Returns Π f_{6u+2,Q_i}(P_i). `running` must have one slot per pair;
+/// on return slot i holds [6u + 2]Q_i.
+fn ate_loop(
+    pairs: &[(G1Affine, G2Affine)],
+    running: &mut [G2Affine],
+    prepared: &mut [PreparedPair],
+) -> Option<Fq12> {
     let mut f = Fq12::one();
-    let mut r = *q; // Running point in G2
 
-    // Get bits of ate loop count (6u + 2)
-    let bits = get_bits(&ATE_LOOP_COUNT);
+    // Running points in G2, seeded by the top digit
+    for ((_, q), r) in pairs.iter().zip(running.iter_mut()) {
+        *r = *q;
+    }
 
-    // Process bits from most significant to least significant (skip leading 1)
-    for i in (0..bits.len() - 1).rev() {
-        // Double step: f = f^2 * l_{R,R}(P)
-        let (line, doubled) = double_step(&r, p);
-        f = f.square().mul(&line);
-        r = doubled;
+    // Process digits from most significant to least significant (skip leading 1)
+    for i in (0..ATE_LOOP_COUNT_NAF.len() - 1).rev() {
+        // One squaring of the shared accumulator per digit
+        f = f.square();
+
+        // Double step: f = f * l_{R,R}(P) for every pair
+        for ((p, _), r) in pairs.iter().zip(running.iter_mut()) {
+            (f, *r) = double_step(&f, r, p);
+        }
+        f = mul_prepared_lines(f, prepared)?;
 
-        // Add step if bit is 1: f = f * l_{R,Q}(P)
-        if bits[i] {
-            let (line, added) = add_step(&r, q, p);
-            f = f.mul(&line);
-            r = added;
+        // Add step for a non-zero digit: f = f * l_{R,±Q}(P)
+        let digit = ATE_LOOP_COUNT_NAF[i];
+        if digit == 0 {
+            continue;
+        }
+        for ((p, q), r) in pairs.iter().zip(running.iter_mut()) {
+            let addend = if digit > 0 { *q } else { q.neg() };
+            (f, *r) = add_step(&f, r, &addend, p);
         }
+        f = mul_prepared_lines(f, prepared)?;
     }
 
-    f
+    Some(f)
 }
 
-/// Double step in Miller loop
-///
-/// Computes the line function l_{T,T}(P) for point doubling
-/// Returns: (line_evaluation, 2T)
-fn double_step(t: &G2Affine, p: &G1Affine) -> (Fq12, G2Affine) {
-    // Tangent line at T: λ = (3*x^2) / (2*y)
-    let three = Fq::from_montgomery([3, 0, 0, 0]);
-    let two = Fq::from_montgomery([2, 0, 0, 0]);
+/// Multiply f by the next precomputed line of every prepared pair
+fn mul_prepared_lines(mut f: Fq12, prepared: &mut [PreparedPair]) -> Option<Fq12> {
+    for (p, lines) in prepared.iter_mut() {
+        let line = lines.next()?;
+        // The stream advances even when P = O contributes nothing
+        if !p.is_infinity() {
+            f = line.mul_evaluated(&f, p);
+        }
+    }
+    Some(f)
+}
 
-    let x_sq = t.x.square();
-    let three_fq2 = Fq2::new(three, Fq::zero());
-    let two_fq2 = Fq2::new(two, Fq::zero());
+/// Precompute the Miller-loop lines for Q
+///
+/// The lines depend only on Q, so for a fixed G2 point (such as the β, γ, δ
+/// of a verifying key) they can be derived once and evaluated at each new
+/// G1 point. Returns `None` for the identity or a point whose loop hits a
+/// vertical line, which cannot happen for a point of G2.
+pub fn prepare_g2(q: &G2Affine) -> Option<[LineCoeffs; MILLER_LINE_COUNT]> {
+    if q.is_infinity() {
+        return None;
+    }
 
-    let numerator = three_fq2.mul(&x_sq);
-    let denominator = two_fq2.mul(&t.y);
-    let lambda = numerator.mul(&denominator.inverse().unwrap());
+    let mut lines = [LineCoeffs::zero(); MILLER_LINE_COUNT];
+    let mut count = 0;
+    let mut r = *q;
+    let neg_q = q.neg();
 
-    // Compute 2T
-    let two_t = t.double();
+    for i in (0..ATE_LOOP_COUNT_NAF.len() - 1).rev() {
+        let (line, doubled) = double_line(&r)?;
+        lines[count] = line;
+        count += 1;
+        r = doubled;
 
-    // Line function evaluation: l(P) ∈ Fq12
-    // l(x,y) = y - y_T - λ(x - x_T)
-    // Embed into Fq12 via the sparse representation
+        let addend = match ATE_LOOP_COUNT_NAF[i] {
+            1 => q,
+            -1 => &neg_q,
+            _ => continue,
+        };
+        let (line, added) = add_line(&r, addend)?;
+        lines[count] = line;
+        count += 1;
+        r = added;
+    }
 
-    let line = compute_line_function(t, &two_t, &lambda, p);
+    // Frobenius correction steps
+    let (line, r) = add_line(&r, &q.psi())?;
+    lines[count] = line;
+    let (line, _) = add_line(&r, &q.psi2().neg())?;
+    lines[count + 1] = line;
 
-    (line, two_t)
+    Some(lines)
 }
 
-/// Add step in Miller loop
+/// Coefficients of one Miller-loop line, independent of the G1 point
 ///
-/// Computes the line function l_{T,Q}(P) for point addition
-/// Returns: (line_evaluation, T + Q)
-fn add_step(t: &G2Affine, q: &G2Affine, p: &G1Affine) -> (Fq12, G2Affine) {
+/// A line of slope λ through T is fixed by λ and c = λ * x_T - y_T; the G1
+/// point only enters when the line is evaluated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LineCoeffs {
+    pub lambda: Fq2,
+    pub c: Fq2,
+}
+
+impl LineCoeffs {
+    pub fn zero() -> Self {
+        LineCoeffs {
+            lambda: Fq2::zero(),
+            c: Fq2::zero(),
+        }
+    }
+
+    fn new(t: &G2Affine, lambda: &Fq2) -> Self {
+        LineCoeffs {
+            lambda: *lambda,
+            c: lambda.mul(&t.x).sub(&t.y),
+        }
+    }
+
+    /// Evaluate the line at P ∈ G1
+    ///
+    /// G2 points live on the D-type sextic twist E'(Fq2): y^2 = x^3 + 3/ξ and map
+    /// into E(Fq12) through the untwist (x, y) -> (x * w^2, y * w^3), where
+    /// w^2 = v and w^6 = ξ. A line of slope λ through T on the twist has slope
+    /// λ * w on E, so evaluated at P = (x_P, y_P) ∈ G1:
+    ///
+    /// l(P) = y_P - y_T * w^3 - λ * w * (x_P - x_T * w^2)
+    ///      = y_P + (-λ * x_P) * w + (λ * x_T - y_T) * v * w
+    ///
+    /// This is a sparse Fq12 element with only three non-zero Fq2 slots:
+    /// c0.c0 = y_P, c1.c0 = -λ * x_P and c1.c1 = λ * x_T - y_T.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn evaluate(&self, p: &G1Affine) -> Fq12 {
+        let c0 = Fq2::new(p.y, Fq::zero());
+        let c3 = self.lambda.mul_by_fq(&p.x).neg();
+
+        Fq12::new(
+            Fq6::new(c0, Fq2::zero(), Fq2::zero()),
+            Fq6::new(c3, self.c, Fq2::zero()),
+        )
+    }
+
+    /// f * l(P), using the sparsity of the line instead of a dense product
+    pub fn mul_evaluated(&self, f: &Fq12, p: &G1Affine) -> Fq12 {
+        let c3 = self.lambda.mul_by_fq(&p.x).neg();
+        f.mul_by_034(&p.y, &c3, &self.c)
+    }
+}
+
+/// Tangent line at T and 2T; `None` if the tangent is vertical (y_T = 0)
+fn double_line(t: &G2Affine) -> Option<(LineCoeffs, G2Affine)> {
+    // Tangent line at T: λ = (3*x^2) / (2*y)
+    let x_sq = t.x.square();
+    let numerator = x_sq.add(&x_sq).add(&x_sq);
+    let denominator = t.y.add(&t.y);
+    let lambda = numerator.mul(&denominator.inverse()?);
+
+    Some((LineCoeffs::new(t, &lambda), chord_point(t, &t.x, &lambda)))
+}
+
+/// Line through T and Q and T + Q; `None` if the line is vertical (T = -Q)
+fn add_line(t: &G2Affine, q: &G2Affine) -> Option<(LineCoeffs, G2Affine)> {
+    if t.x == q.x {
+        if t.y == q.y {
+            return double_line(t);
+        }
+        return None;
+    }
+
     // Line through T and Q: λ = (y_Q - y_T) / (x_Q - x_T)
     let dy = q.y.sub(&t.y);
     let dx = q.x.sub(&t.x);
-    let lambda = dy.mul(&dx.inverse().unwrap());
+    let lambda = dy.mul(&dx.inverse()?);
 
-    // Compute T + Q
-    let sum = t.add(q);
+    Some((LineCoeffs::new(t, &lambda), chord_point(t, &q.x, &lambda)))
+}
 
-    // Line function evaluation
-    let line = compute_line_function(t, q, &lambda, p);
+/// T + Q from the slope λ of the line through them, so the group operation
+/// reuses the line's inversion instead of paying for its own
+fn chord_point(t: &G2Affine, x_q: &Fq2, lambda: &Fq2) -> G2Affine {
+    // x3 = λ^2 - x_T - x_Q, y3 = λ(x_T - x3) - y_T
+    let x3 = lambda.square().sub(&t.x).sub(x_q);
+    let y3 = lambda.mul(&t.x.sub(&x3)).sub(&t.y);
 
-    (line, sum)
+    G2Affine::new(x3, y3)
 }
 
-/// Compute line function evaluation
+/// Double step in Miller loop
 ///
-/// The line through points on G2 evaluated at a point on G1,
-/// embedded into Fq12 using sparse multiplication
-fn compute_line_function(
-    t: &G2Affine,
-    _r: &G2Affine,
-    lambda: &Fq2,
-    _p: &G1Affine,
-) -> Fq12 {
-    // Line function: l(P) = y_P * A - x_P * B - C
-    // where A, B, C depend on the slope λ and points T, R
-
-    // This is a sparse element of Fq12: only some coefficients are non-zero
-    // We can optimize by computing only the non-zero parts
-
-    // For BN254, the embedding is:
-    // l(P) has form (c0, 0, c2, 0, c4, 0) in Fq6[2] representation
-    // where c0, c2, c4 ∈ Fq2
-
-    // Simplified computation (full version requires careful coefficient tracking)
-    let c0 = lambda.mul(&t.x).sub(&t.y);
-    let c1 = lambda;
-
-    // Build Fq12 element from sparse representation
-    // In practice, this is optimized using sparse multiplication
-    let fq6_c0 = Fq6::new(c0.clone(), Fq2::zero(), Fq2::zero());
-    let fq6_c1 = Fq6::new(c1.clone(), Fq2::zero(), Fq2::zero());
-
-    Fq12::new(fq6_c0, fq6_c1)
+/// Multiplies f by the line function l_{T,T}(P) for point doubling
+/// Returns: (f * l_{T,T}(P), 2T)
+fn double_step(f: &Fq12, t: &G2Affine, p: &G1Affine) -> (Fq12, G2Affine) {
+    let (line, two_t) = double_line(t).unwrap();
+
+    (line.mul_evaluated(f, p), two_t)
+}
+
+/// Add step in Miller loop
+///
+/// Multiplies f by the line function l_{T,Q}(P) for point addition
+/// Returns: (f * l_{T,Q}(P), T + Q)
+fn add_step(f: &Fq12, t: &G2Affine, q: &G2Affine, p: &G1Affine) -> (Fq12, G2Affine) {
+    match add_line(t, q) {
+        Some((line, sum)) => (line.mul_evaluated(f, p), sum),
+        // T = -Q: the vertical line x - x_T lies in Fq6 and is
+        // eliminated by the final exponentiation
+        None => (*f, G2Affine::infinity()),
+    }
 }
 
 /// Final exponentiation: raise f to (p^12 - 1) / r
@@ -183,7 +414,11 @@ fn compute_line_function(
 ///
 /// The exponent can be factored as:
 /// (p^12 - 1) / r = (p^6 - 1) * (p^2 + 1) * (p^4 - p^2 + 1) / r
-fn final_exponentiation(f: &Fq12) -> Fq12 {
+///
+/// The hard part actually raises to a fixed multiple of (p^4 - p^2 + 1) / r
+/// (see `hard_part`), which is what snarkjs/ffjavascript compute as well, so
+/// GT values such as `vk_alphabeta_12` can be compared directly.
+pub(crate) fn final_exponentiation(f: &Fq12) -> Fq12 {
     // Easy part: (p^6 - 1)(p^2 + 1)
     let f1 = easy_part(f);
 
@@ -192,9 +427,12 @@ fn final_exponentiation(f: &Fq12) -> Fq12 {
 }
 
 /// Easy part of final exponentiation: (p^6 - 1)(p^2 + 1)
+///
+/// The result lies in the cyclotomic subgroup, where inversion is
+/// conjugation and `Fq12::cyclotomic_square` applies.
 fn easy_part(f: &Fq12) -> Fq12 {
     // f^(p^6 - 1)
-    let f1 = f.frobenius_map(6);
+    let f1 = f.conjugate();
     let f2 = f.inverse().unwrap();
     let t0 = f1.mul(&f2);
 
@@ -205,77 +443,49 @@ fn easy_part(f: &Fq12) -> Fq12 {
 
 /// Hard part of final exponentiation: (p^4 - p^2 + 1) / r
 ///
-/// Uses the efficient algorithm for BN curves based on the curve parameter u
-/// This follows the optimized algorithm from Scott et al.:
-/// "On the Final Exponentiation for Calculating Pairings on Ordinary Elliptic Curves"
+/// Follows Fuentes-Castañeda, Knapp and Rodríguez-Henríquez,
+/// "Faster Hashing to G2", which computes
+///
+/// f^(p^3 * (12u^3 + 6u^2 + 4u - 1) +
+///    p^2 * (12u^3 + 6u^2 + 6u) +
+///    p   * (12u^3 + 6u^2 + 4u) +
+///    1   * (12u^3 + 12u^2 + 6u + 1))
+///
+/// = f^(2u * (6u^2 + 3u + 1) * (p^4 - p^2 + 1) / r)
+///
+/// with three exponentiations by u, each done with cyclotomic squarings.
+/// The extra factor 2u(6u^2 + 3u + 1) is coprime to r, so the result is
+/// still a non-degenerate bilinear pairing; it matches the convention used
+/// by snarkjs/ffjavascript and arkworks.
 ///
-/// The algorithm uses an optimal addition chain to minimize operations
+/// `f` must be in the cyclotomic subgroup (the output of `easy_part`).
 fn hard_part(f: &Fq12) -> Fq12 {
-    let u = [BN_U, 0, 0, 0];
-
-    // Step 1: Compute powers of f raised to u
-    let fu = f.pow(&u);
-    let fu2 = fu.pow(&u);
-    let fu3 = fu2.pow(&u);
-
-    // Step 2: Compute Frobenius maps
-    let fp = f.frobenius_map(1);
-    let fp2 = f.frobenius_map(2);
-    let fp3 = f.frobenius_map(3);
-
-    // Step 3: Compute inverse needed for the formula
-    let fu2_inv = fu2.inverse().unwrap();
-
-    // Step 4: Optimal addition chain computation
-    // y0 = f^(u+1)
-    let y0 = fu.mul(f);
-
-    // y1 = f^(u^2)
-    let y1 = fu2;
-
-    // y2 = f^(u^3)
-    let y2 = fu3;
-
-    // y3 = f^p
-    let y3 = fp;
-
-    // y4 = f^(p^2)
-    let y4 = fp2;
-
-    // y5 = f^(p^3)
-    let y5 = fp3;
-
-    // y6 = y1^p = f^(u^2 * p)
-    let y6 = y1.frobenius_map(1);
-
-    // Combine terms according to the BN254 final exponentiation formula
-    // The formula is optimized for BN curves with parameter u
-    // Result = y3 * y4 * y5 * y0 * y2 * y6 * (y1^(-1))
-
-    let t0 = y3.mul(&y4).mul(&y5);
-    let t1 = y0.mul(&y2).mul(&y6);
-    let t2 = t0.mul(&t1);
-    let t3 = t2.mul(&fu2_inv);
-
-    t3
+    let y0 = exp_by_neg_u(f);
+    let y1 = y0.cyclotomic_square();
+    let y2 = y1.cyclotomic_square();
+    let y3 = y2.mul(&y1);
+    let y4 = exp_by_neg_u(&y3);
+    let y5 = y4.cyclotomic_square();
+    let y6 = exp_by_neg_u(&y5);
+    let y3 = y3.conjugate();
+    let y6 = y6.conjugate();
+    let y7 = y6.mul(&y4);
+    let y8 = y7.mul(&y3);
+    let y9 = y8.mul(&y1);
+    let y10 = y8.mul(&y4);
+    let y11 = y10.mul(f);
+    let y12 = y9.frobenius_map(1);
+    let y13 = y12.mul(&y11);
+    let y8 = y8.frobenius_map(2);
+    let y14 = y8.mul(&y13);
+    let y15 = f.conjugate().mul(&y9).frobenius_map(3);
+
+    y15.mul(&y14)
 }
 
-/// Extract bits from a limb array (for Miller loop iteration)
-/// Returns a fixed-size array of bits (max 64 bits for BN254 ate loop count)
-fn get_bits(limbs: &[u64]) -> [bool; 64] {
-    let mut bits = [false; 64];
-    let mut bit_count = 0;
-
-    for &limb in limbs.iter() {
-        for i in 0..64 {
-            if bit_count < 64 {
-                bits[bit_count] = (limb >> i) & 1 == 1;
-                bit_count += 1;
-            }
-        }
-    }
-
-    bits
+/// f^(-u) for f in the cyclotomic subgroup
+fn exp_by_neg_u(f: &Fq12) -> Fq12 {
+    f.cyclotomic_exp(BN_U).conjugate()
 }
 
 /// Pairing check: verify that e(A, B) * e(C, D) * ... = 1
@@ -287,10 +497,23 @@ pub fn pairing_check(pairs: &[(G1Affine, G2Affine)]) -> bool {
     result.is_one()
 }
 
+/// Pairing check with some G2 points given by their precomputed lines
+///
+/// Returns `None` if a line stream is malformed (see
+/// `multi_miller_loop_prepared`).
+#[cfg_attr(not(test), allow(dead_code))]
+pub fn pairing_check_prepared(
+    pairs: &[(G1Affine, G2Affine)],
+    prepared: &mut [PreparedPair],
+) -> Option<bool> {
+    Some(multi_pairing_prepared(pairs, prepared)?.is_one())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::field::Fq;
+    use crate::field::op_count;
+    use crate::scalar::Fr;
 
     #[test]
     fn test_pairing_identity() {
@@ -336,7 +559,7 @@ mod tests {
     #[test]
     fn test_multi_pairing_empty() {
         // Empty product should return identity
-        let pairs: Vec<(G1Affine, G2Affine)> = vec![];
+        let pairs: [(G1Affine, G2Affine); 0] = [];
         let result = multi_pairing(&pairs);
         assert!(result.is_one());
     }
@@ -384,39 +607,267 @@ mod tests {
         assert!(result.is_one());
     }
 
+    /// Decode 12 big-endian hex coefficients, ordered c0.c0.c0, c0.c0.c1,
+    /// c0.c1.c0, ..., c1.c2.c1 (the nesting snarkjs uses for GT values)
+    fn fq12_from_hex(coeffs: [&str; 12]) -> Fq12 {
+        let mut fq = [Fq::zero(); 12];
+        for (out, hex) in fq.iter_mut().zip(coeffs.iter()) {
+            let mut bytes = [0u8; 32];
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).unwrap();
+            }
+            *out = Fq::from_bytes_be(&bytes).unwrap();
+        }
+        let fq2 = |i: usize| Fq2::new(fq[2 * i], fq[2 * i + 1]);
+        Fq12::new(
+            Fq6::new(fq2(0), fq2(1), fq2(2)),
+            Fq6::new(fq2(3), fq2(4), fq2(5)),
+        )
+    }
+
+    /// Fq12 element with coefficients 1, 2, ..., 12
+    fn small_fq12() -> Fq12 {
+        let mut fq = [Fq::zero(); 12];
+        let mut acc = Fq::zero();
+        for coeff in fq.iter_mut() {
+            acc = acc.add(&Fq::one());
+            *coeff = acc;
+        }
+        let fq2 = |i: usize| Fq2::new(fq[2 * i], fq[2 * i + 1]);
+        Fq12::new(
+            Fq6::new(fq2(0), fq2(1), fq2(2)),
+            Fq6::new(fq2(3), fq2(4), fq2(5)),
+        )
+    }
+
+    #[test]
+    fn test_final_exponentiation_reference_value() {
+        // Reference computed with an independent big-integer implementation
+        // as f^((p^12 - 1) / r * 2u(6u^2 + 3u + 1)) for f = small_fq12()
+        let expected = fq12_from_hex([
+            "28b180e278ec852f2399772b8e4cc1e6dfc75dbabb0f59ccbc73394761237306",
+            "0a0cafe3a5b0a235244558b59ff276b011668e2e7765a893e427070383dc707a",
+            "2e6ce88e187b31bb0a3e1edb25f32db29519e8930cd7df4df55ef6227d9dde3c",
+            "06d85521375dd58cc48a1a1e75f4dc9d08202a85f70629861a7c5a5579100873",
+            "2630bd780a949660aadb51fd671d873c2548e43fbe0efd746eb7f54625e13953",
+            "10815c8df0b0c53bf520670f5a9b828b6001e0396e644f889ea00fc2761af2d4",
+            "30593713675abc272dbba010455afeed0e93bf28bcb8d57ba0c4218bc3ebdbe5",
+            "08d1a38ddcb0c340355bf8188e12afca50a05849266ac3f167f6a6e62ca07756",
+            "2982f633fa3a364d54937faf322a50b0e4da54a4eddc4578ced25caf05a8fd78",
+            "0f60089179fb3542e8ba3d16748883e22b7e0ad95e75b0cd2918b8e40424854a",
+            "0a24498b24a5cd43fd9226fbb777aa3d83693288d920a60a9aea2024b611105e",
+            "2f268eba71cba8a84c38eab1c431dc33951f156330462a7519f55cf3a2aa3436",
+        ]);
+
+        assert_eq!(final_exponentiation(&small_fq12()), expected);
+    }
+
+    #[test]
+    fn test_final_exponentiation_lands_in_gt() {
+        // The output has order r: x^r == 1
+        let result = final_exponentiation(&small_fq12());
+        assert!(!result.is_one());
+        assert!(result.pow(&crate::curve::SUBGROUP_ORDER).is_one());
+    }
+
+    #[test]
+    fn test_pairing_generator_reference_value() {
+        // e(G1, G2) as computed by snarkjs/ffjavascript-compatible pairing
+        let expected = fq12_from_hex([
+            "262b253feda94cfe0da01bde280a3ed6f87e5feb898578b55e1f63739d870e95",
+            "02e02d2cc795a2000a1b1f823879abbd397c4dea0918ed66b49d34b48efb8a4a",
+            "13a9f2d6e29b128da5b1ad44b31977935fd2957387ecb1fc4e135402fdbd1de0",
+            "040ba9fa500f1a5c4b31984a74e68659c4b420bd699ce630b130b08a6ea1162b",
+            "0afc2f3fd870678fbe359d7f9873f052478f590b211ce30bf5e3eeaef89eafdb",
+            "1c54a530398c9064bdc662d929e645cadda9a712cc5a8243f9cddbd2d98dd1f0",
+            "095c0fbf5d5a1ac023794a0d856f92591ba990ecfd4b7aef5c0d58c5dc2429fe",
+            "14d3d6ca72d8a950a31dc10f7b4053c9e9ad9ebb590cb4a60f8215d4b99f2b4a",
+            "1dc0e7bbc3d70e6689dc206b4b91c85759dc1a23043c585fdfaf545838ca7429",
+            "0b53320e5a6488cb98a855ffc837d2a75ab90d61ac16cc1b7ab2cd3ed5e22b97",
+            "13a8afd3085dae4c6c91476ef36cd1d318ce07bac42a9c0f9bd7fddaf5ebd723",
+            "00f97b5221474526b601f3730a3afa965ceee1b343940c383e5314859e762c97",
+        ]);
+
+        assert_eq!(
+            pairing(&G1Affine::generator(), &G2Affine::generator()),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_miller_loop_takes_one_inversion_per_line() {
+        // Each affine line step inverts once, for the line and the new point
+        op_count::reset();
+        let _ = miller_loop(&G1Affine::generator(), &G2Affine::generator());
+        assert_eq!(op_count::fq_inversions(), MILLER_LINE_COUNT as u64);
+    }
+
+    #[test]
+    fn test_ate_loop_count_naf_covers_every_bit() {
+        // Regression: the loop count is 65 bits wide, so a 64-bit
+        // representation silently drops the top bit
+        let mut value: i128 = 0;
+        for (i, &digit) in ATE_LOOP_COUNT_NAF.iter().enumerate() {
+            assert!((-1..=1).contains(&digit));
+            value += (digit as i128) << i;
+        }
+
+        let expected = ((ATE_LOOP_COUNT[1] as i128) << 64) | ATE_LOOP_COUNT[0] as i128;
+        assert_eq!(value, expected);
+        assert_eq!(value, 6 * BN_U as i128 + 2);
+        assert_eq!(ATE_LOOP_COUNT_NAF[65], 1);
+    }
+
+    #[test]
+    fn test_ate_loop_count_naf_is_non_adjacent() {
+        for (i, pair) in ATE_LOOP_COUNT_NAF.windows(2).enumerate() {
+            assert!(
+                pair[0] == 0 || pair[1] == 0,
+                "digits {} and {} are both non-zero",
+                i,
+                i + 1
+            );
+        }
+
+        // One doubling per digit below the top one, one addition per
+        // non-zero digit among them, and the two Frobenius steps
+        let lower = &ATE_LOOP_COUNT_NAF[..ATE_LOOP_COUNT_NAF.len() - 1];
+        let additions = lower.iter().filter(|&&digit| digit != 0).count();
+        assert_eq!(lower.len() + additions + 2, MILLER_LINE_COUNT);
+    }
+
+    #[test]
+    fn test_ate_loop_processes_every_digit() {
+        // The running point must end at [6u + 2]Q
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+
+        let g2_3 = g2.mul(&Fr::from_u64(3));
+
+        let mut running = [G2Affine::infinity(); 2];
+        ate_loop(&[(g1, g2), (g1, g2_3)], &mut running, &mut []).unwrap();
+        let loop_count = [ATE_LOOP_COUNT[0], ATE_LOOP_COUNT[1], 0, 0];
+        assert_eq!(running[0], g2.mul_limbs(&loop_count));
+        assert_eq!(running[1], g2_3.mul_limbs(&loop_count));
+    }
+
+    #[test]
+    fn test_multi_miller_loop_matches_separate_loops() {
+        // Sharing the accumulator must not change the product of the loops,
+        // including across chunk boundaries and with skipped infinity pairs
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+
+        let mut pairs = [(G1Affine::infinity(), G2Affine::infinity()); MULTI_MILLER_WIDTH + 2];
+        for (k, pair) in pairs.iter_mut().enumerate() {
+            let k = k as u64 + 1;
+            *pair = (g1.mul(&Fr::from_u64(k)), g2.mul(&Fr::from_u64(k + 1)));
+        }
+        pairs[3].0 = G1Affine::infinity();
+
+        let separate = |pairs: &[(G1Affine, G2Affine)]| {
+            let mut f = Fq12::one();
+            for (p, q) in pairs.iter().filter(|(p, _)| !p.is_infinity()) {
+                f = f.mul(&miller_loop(p, q));
+            }
+            f
+        };
+
+        assert_eq!(multi_miller_loop(&pairs), separate(&pairs));
+        assert_eq!(multi_miller_loop(&pairs[..4]), separate(&pairs[..4]));
+    }
+
+    #[test]
+    fn test_prepare_g2_matches_miller_loop() {
+        let p = G1Affine::generator().mul(&Fr::from_u64(5));
+        let q = G2Affine::generator().mul(&Fr::from_u64(7));
+
+        let lines = prepare_g2(&q).unwrap();
+        let mut stream = lines.iter().copied();
+        let f = multi_miller_loop_prepared(&[], &mut [(p, &mut stream)]).unwrap();
+
+        assert_eq!(f, miller_loop(&p, &q));
+        assert!(prepare_g2(&G2Affine::infinity()).is_none());
+    }
+
+    #[test]
+    fn test_multi_miller_loop_prepared_mixes_pairs() {
+        // Prepared pairs share the accumulator with regular ones, across a
+        // chunk boundary and with a G1 point at infinity
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+
+        let mut pairs = [(G1Affine::infinity(), G2Affine::infinity()); MULTI_MILLER_WIDTH + 1];
+        for (k, pair) in pairs.iter_mut().enumerate() {
+            let k = k as u64 + 1;
+            *pair = (g1.mul(&Fr::from_u64(k)), g2.mul(&Fr::from_u64(k + 2)));
+        }
+        let p1 = g1.mul(&Fr::from_u64(3));
+        let q1 = g2.mul(&Fr::from_u64(4));
+        let q2 = g2.mul(&Fr::from_u64(6));
+
+        let lines1 = prepare_g2(&q1).unwrap();
+        let lines2 = prepare_g2(&q2).unwrap();
+        let mut stream1 = lines1.iter().copied();
+        let mut stream2 = lines2.iter().copied();
+        let f = multi_miller_loop_prepared(
+            &pairs,
+            &mut [(p1, &mut stream1), (G1Affine::infinity(), &mut stream2)],
+        )
+        .unwrap();
+
+        assert_eq!(f, multi_miller_loop(&pairs).mul(&miller_loop(&p1, &q1)));
+    }
+
     #[test]
-    fn test_get_bits() {
-        // Test bit extraction utility
-        let limbs = [0b1011];
-        let bits = get_bits(&limbs);
-        // Check first 4 bits
-        assert_eq!(bits[0], true);
-        assert_eq!(bits[1], true);
-        assert_eq!(bits[2], false);
-        assert_eq!(bits[3], true);
-
-        // Test with zero
-        let limbs_zero = [0];
-        let bits_zero = get_bits(&limbs_zero);
-        assert!(!bits_zero[0]);
+    fn test_multi_miller_loop_prepared_rejects_wrong_line_count() {
+        let p = G1Affine::generator();
+        let lines = prepare_g2(&G2Affine::generator()).unwrap();
+
+        let mut short = lines[..MILLER_LINE_COUNT - 1].iter().copied();
+        assert!(multi_miller_loop_prepared(&[], &mut [(p, &mut short)]).is_none());
+
+        let mut long = lines.iter().chain(lines[..1].iter()).copied();
+        assert!(multi_miller_loop_prepared(&[], &mut [(p, &mut long)]).is_none());
     }
 
     #[test]
-    fn test_get_bits_multiple_limbs() {
-        // Test with two limbs
-        let limbs = [0x1];
-        let bits = get_bits(&limbs);
-        // Should have bit 0 set
-        assert_eq!(bits[0], true);
-        assert_eq!(bits[1], false);
+    fn test_pairing_check_prepared() {
+        // e(aP, Q) * e(-P, aQ) = 1 with aQ prepared
+        let a = Fr::from_u64(9);
+        let p = G1Affine::generator();
+        let q = G2Affine::generator();
+
+        let lines = prepare_g2(&q.mul(&a)).unwrap();
+        let mut stream = lines.iter().copied();
+        let pairs = [(p.mul(&a), q)];
+        assert_eq!(
+            pairing_check_prepared(&pairs, &mut [(p.neg(), &mut stream)]),
+            Some(true)
+        );
+
+        let mut stream = lines.iter().copied();
+        assert_eq!(
+            pairing_check_prepared(&pairs, &mut [(p, &mut stream)]),
+            Some(false)
+        );
     }
 
     #[test]
-    fn test_ate_loop_count_bits() {
-        // Verify ATE_LOOP_COUNT produces valid bit representation
-        let bits = get_bits(&ATE_LOOP_COUNT);
-        // Should have at least one bit set
-        assert!(bits.iter().any(|&b| b));
+    fn test_frobenius_correction_points() {
+        // [6u + 2]Q + π(Q) - π²(Q) + π³(Q) = O for Q in G2
+        let g2 = G2Affine::generator();
+        let q1 = g2.psi();
+        let neg_q2 = g2.psi2().neg();
+        let q3 = q1.psi().psi();
+
+        assert!(q1.is_on_curve());
+        assert!(neg_q2.is_on_curve());
+        assert_eq!(neg_q2, q1.psi().neg());
+
+        let mut running = [G2Affine::infinity()];
+        ate_loop(&[(G1Affine::generator(), g2)], &mut running, &mut []).unwrap();
+        assert!(running[0].add(&q1).add(&neg_q2).add(&q3).is_infinity());
     }
 
     #[test]
@@ -425,7 +876,7 @@ mod tests {
         let g1 = G1Affine::generator();
         let g2 = G2Affine::generator();
 
-        let (line, doubled) = double_step(&g2, &g1);
+        let (line, doubled) = double_step(&Fq12::one(), &g2, &g1);
 
         assert!(!line.is_zero(), "Line function should not be zero");
         assert!(!doubled.is_infinity(), "Doubled point should not be infinity");
@@ -438,22 +889,106 @@ mod tests {
         let g2 = G2Affine::generator();
         let g2_double = g2.double();
 
-        let (line, sum) = add_step(&g2, &g2_double, &g1);
+        let (line, sum) = add_step(&Fq12::one(), &g2, &g2_double, &g1);
 
         assert!(!line.is_zero(), "Line function should not be zero");
         assert!(!sum.is_infinity(), "Sum point should not be infinity");
     }
 
+    /// Untwist a G2 point into E(Fq12): (x, y) -> (x * w^2, y * w^3)
+    fn untwist(q: &G2Affine) -> (Fq12, Fq12) {
+        let x = Fq12::new(Fq6::new(Fq2::zero(), q.x, Fq2::zero()), Fq6::zero());
+        let y = Fq12::new(Fq6::zero(), Fq6::new(Fq2::zero(), q.y, Fq2::zero()));
+        (x, y)
+    }
+
+    fn fq_to_fq12(a: &Fq) -> Fq12 {
+        Fq12::new(
+            Fq6::new(Fq2::new(*a, Fq::zero()), Fq2::zero(), Fq2::zero()),
+            Fq6::zero(),
+        )
+    }
+
+    /// Reference line evaluation with dense Fq12 arithmetic on the untwisted points
+    fn dense_line(t: &G2Affine, r: &G2Affine, p: &G1Affine) -> Fq12 {
+        let (xt, yt) = untwist(t);
+        let (xr, yr) = untwist(r);
+        let (xp, yp) = (fq_to_fq12(&p.x), fq_to_fq12(&p.y));
+
+        let slope = if t == r {
+            let three = fq_to_fq12(&Fq::one().add(&Fq::one()).add(&Fq::one()));
+            let x_sq = xt.square();
+            three.mul(&x_sq).mul(&yt.add(&yt).inverse().unwrap())
+        } else {
+            yr.sub(&yt).mul(&xr.sub(&xt).inverse().unwrap())
+        };
+
+        yp.sub(&yt).sub(&slope.mul(&xp.sub(&xt)))
+    }
+
     #[test]
     fn test_compute_line_function() {
-        // Verify line function computation doesn't panic
+        // The sparse evaluation must agree with the dense line through the
+        // untwisted points, evaluated at P
         let g1 = G1Affine::generator();
+        let p = g1.mul(&Fr::from_u64(5));
         let g2 = G2Affine::generator();
-        let lambda = Fq2::one();
+        let g2_double = g2.double();
+
+        let (tangent, _) = double_step(&Fq12::one(), &g2, &p);
+        assert_eq!(tangent, dense_line(&g2, &g2, &p));
+
+        let (chord, _) = add_step(&Fq12::one(), &g2_double, &g2, &p);
+        assert_eq!(chord, dense_line(&g2_double, &g2, &p));
+    }
+
+    #[test]
+    fn test_line_mul_evaluated_matches_dense_mul() {
+        let p = G1Affine::generator().mul(&Fr::from_u64(3));
+        let f = miller_loop(&p, &G2Affine::generator());
+        let (line, _) = double_line(&G2Affine::generator().mul(&Fr::from_u64(5))).unwrap();
+
+        assert_eq!(line.mul_evaluated(&f, &p), f.mul(&line.evaluate(&p)));
+    }
+
+    #[test]
+    fn test_compute_line_function_depends_on_p() {
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+
+        let (line_p, _) = double_step(&Fq12::one(), &g2, &g1);
+        let (line_2p, _) = double_step(&Fq12::one(), &g2, &g1.double());
+        let (line_neg_p, _) = double_step(&Fq12::one(), &g2, &g1.neg());
 
-        let line = compute_line_function(&g2, &g2, &lambda, &g1);
+        assert_ne!(line_p, line_2p);
+        assert_ne!(line_p, line_neg_p);
+    }
+
+    #[test]
+    fn test_pairing_bilinearity() {
+        // e(aP, bQ) == e(P, Q)^(ab)
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let e = pairing(&g1, &g2);
+
+        for (a, b) in [(2u64, 3u64), (5, 7), (0x1234_5678, 0x9abc)] {
+            let lhs = pairing(&g1.mul(&Fr::from_u64(a)), &g2.mul(&Fr::from_u64(b)));
+            let rhs = e.pow(&[a * b, 0, 0, 0]);
+            assert_eq!(lhs, rhs, "a = {}, b = {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_pairing_bilinearity_per_argument() {
+        // e(aP, Q) == e(P, aQ) == e(P, Q)^a
+        let g1 = G1Affine::generator();
+        let g2 = G2Affine::generator();
+        let e = pairing(&g1, &g2);
+        let a = [0xdead_beef, 0, 0, 0];
 
-        // Line function should be a valid Fq12 element
-        assert!(!line.is_zero() || line.is_zero()); // Should not panic
+        let e_ap = pairing(&g1.mul_limbs(&a), &g2);
+        let e_aq = pairing(&g1, &g2.mul_limbs(&a));
+        assert_eq!(e_ap, e.pow(&a));
+        assert_eq!(e_aq, e.pow(&a));
     }
 }
@@ -0,0 +1,162 @@
+// Verifying-Key Registry
+// `register_vk` validates a verifying key once and stores it in persistent
+// storage, along with its prepared form, under the SHA-256 of its canonical
+// encoding. `verify` then takes only that ID: callers neither ship the IC
+// points with every proof nor get to substitute a key of their own.
+// Registering and deprecating keys are gated by the roles in `access`.
+
+use soroban_sdk::{contracttype, Bytes, BytesN, Env};
+
+use crate::{Groth16Verifier, PreparedVerifyingKey, VerifierError, VerifyingKey};
+
+/// Ledgers closed per day, at about 5 seconds per ledger
+pub(crate) const LEDGERS_PER_DAY: u32 = 17_280;
+
+/// A registered key's entries are extended whenever they are used with
+/// less than `KEY_TTL_THRESHOLD` ledgers left, up to `KEY_TTL_EXTEND_TO`
+const KEY_TTL_THRESHOLD: u32 = 30 * LEDGERS_PER_DAY;
+const KEY_TTL_EXTEND_TO: u32 = 120 * LEDGERS_PER_DAY;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum DataKey {
+    /// The key as registered, returned by `get_vk`
+    VerifyingKey(BytesN<32>),
+    /// Its prepared form, which `verify` checks proofs against
+    PreparedKey(BytesN<32>),
+    /// Present once the key is deprecated
+    Deprecated(BytesN<32>),
+    /// Index of the public input used as a nullifier (see `nullifier`)
+    NullifierInput(BytesN<32>),
+}
+
+/// SHA-256 over the coordinates of α, β, γ, δ and every IC point, in
+/// declaration order
+///
+/// Only defined for a key that passed validation: every coordinate is then
+/// the unique 32-byte encoding of its value, so equal keys get equal IDs
+/// and the concatenation cannot be split differently.
+pub(crate) fn key_id(env: &Env, vk: &VerifyingKey) -> BytesN<32> {
+    let mut encoding = Bytes::new(env);
+    encoding.append(&vk.alpha.x);
+    encoding.append(&vk.alpha.y);
+    for point in [&vk.beta, &vk.gamma, &vk.delta] {
+        for coordinate in point.x.iter().chain(point.y.iter()) {
+            encoding.append(&coordinate);
+        }
+    }
+    for point in vk.ic.iter() {
+        encoding.append(&point.x);
+        encoding.append(&point.y);
+    }
+    env.crypto().sha256(&encoding).into()
+}
+
+/// Validate `vk` and store it, returning its ID
+///
+/// Registering a key again returns the same ID and leaves storage as it is,
+/// so a deprecated key stays deprecated.
+pub(crate) fn register(env: &Env, vk: &VerifyingKey) -> Result<BytesN<32>, VerifierError> {
+    // Every point is checked before the key is hashed (see `key_id`)
+    let prepared = Groth16Verifier::prepare_vk(env, vk)?;
+    let id = key_id(env, vk);
+
+    let storage = env.storage().persistent();
+    let vk_key = DataKey::VerifyingKey(id.clone());
+    let prepared_key = DataKey::PreparedKey(id.clone());
+    if !storage.has(&vk_key) {
+        storage.set(&vk_key, vk);
+        storage.set(&prepared_key, &prepared);
+    }
+    extend_ttl(env, &id);
+
+    Ok(id)
+}
+
+/// The registered key with ID `id`
+pub(crate) fn verifying_key(env: &Env, id: &BytesN<32>) -> Option<VerifyingKey> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::VerifyingKey(id.clone()))
+}
+
+/// The prepared form of the registered key with ID `id`, keeping its
+/// entries alive
+///
+/// Fails for a deprecated key: it stays readable with `get_vk`, but no
+/// longer verifies proofs.
+pub(crate) fn prepared_key(
+    env: &Env,
+    id: &BytesN<32>,
+) -> Result<PreparedVerifyingKey, VerifierError> {
+    let storage = env.storage().persistent();
+    let prepared = storage
+        .get(&DataKey::PreparedKey(id.clone()))
+        .ok_or(VerifierError::UnknownVerifyingKey)?;
+    if is_deprecated(env, id) {
+        return Err(VerifierError::DeprecatedVerifyingKey);
+    }
+    extend_ttl(env, id);
+    Ok(prepared)
+}
+
+/// Mark the registered key with ID `id` as deprecated, for good
+pub(crate) fn deprecate(env: &Env, id: &BytesN<32>) -> Result<(), VerifierError> {
+    let storage = env.storage().persistent();
+    if !storage.has(&DataKey::VerifyingKey(id.clone())) {
+        return Err(VerifierError::UnknownVerifyingKey);
+    }
+    storage.set(&DataKey::Deprecated(id.clone()), &());
+    extend_ttl(env, id);
+    Ok(())
+}
+
+/// Make public input `index` of the registered key with ID `id` a
+/// nullifier, for good
+pub(crate) fn set_nullifier_input(
+    env: &Env,
+    id: &BytesN<32>,
+    index: u32,
+) -> Result<(), VerifierError> {
+    let vk = verifying_key(env, id).ok_or(VerifierError::UnknownVerifyingKey)?;
+    if index >= vk.ic.len() - 1 {
+        return Err(VerifierError::InvalidNullifierInput);
+    }
+
+    // Moving or dropping the nullifier would let spent values through again
+    let storage = env.storage().persistent();
+    let key = DataKey::NullifierInput(id.clone());
+    if storage.has(&key) {
+        return Err(VerifierError::NullifierInputAlreadySet);
+    }
+    storage.set(&key, &index);
+    extend_ttl(env, id);
+    Ok(())
+}
+
+/// Index of the public input the key with ID `id` uses as a nullifier
+pub(crate) fn nullifier_input(env: &Env, id: &BytesN<32>) -> Option<u32> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::NullifierInput(id.clone()))
+}
+
+pub(crate) fn is_deprecated(env: &Env, id: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Deprecated(id.clone()))
+}
+
+fn extend_ttl(env: &Env, id: &BytesN<32>) {
+    let storage = env.storage().persistent();
+    for key in [
+        DataKey::VerifyingKey(id.clone()),
+        DataKey::PreparedKey(id.clone()),
+        DataKey::Deprecated(id.clone()),
+        DataKey::NullifierInput(id.clone()),
+    ] {
+        if storage.has(&key) {
+            storage.extend_ttl(&key, KEY_TTL_THRESHOLD, KEY_TTL_EXTEND_TO);
+        }
+    }
+}
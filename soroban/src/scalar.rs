@@ -0,0 +1,375 @@
+// BN254 Scalar Field Arithmetic
+// Scalar field modulus (the group order of G1 and G2):
+// r = 21888242871839275222246405745257275088548364400416034343698204186575808495617
+// = 0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001
+
+#![allow(clippy::needless_range_loop)] // limb arithmetic reads clearer with explicit indices
+
+use crate::field::limbs_less_than;
+
+// BN254 scalar field modulus as u64 limbs (little-endian)
+pub const MODULUS: [u64; 4] = [
+    0x43e1f593f0000001,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+];
+
+// Montgomery R = 2^256 mod r
+pub const R: [u64; 4] = [
+    0xac96341c4ffffffb,
+    0x36fc76959f60cd29,
+    0x666ea36f7879462e,
+    0x0e0a77c19a07df2f,
+];
+
+// Montgomery R^2 mod r
+pub const R2: [u64; 4] = [
+    0x1bb8e645ae216da7,
+    0x53fe3ab1e35c59e3,
+    0x8c49833d53bb8085,
+    0x0216d0b17f4e44a5,
+];
+
+// -r^{-1} mod 2^64
+const INV: u64 = 0xc2e1f593efffffff;
+
+/// Element of the scalar field Fr, stored in Montgomery form
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Fr {
+    pub limbs: [u64; 4],
+}
+
+impl Fr {
+    /// Create from Montgomery form
+    pub const fn from_montgomery(limbs: [u64; 4]) -> Self {
+        Fr { limbs }
+    }
+
+    /// Create from a small integer
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn from_u64(value: u64) -> Self {
+        Self::reduce(&[value, 0, 0, 0])
+    }
+
+    /// Create from a canonical integer (little-endian limbs)
+    ///
+    /// Returns `None` unless the integer is strictly less than r.
+    pub fn from_limbs(limbs: &[u64; 4]) -> Option<Self> {
+        if !limbs_less_than(limbs, &MODULUS) {
+            return None;
+        }
+        Some(Self::reduce(limbs))
+    }
+
+    /// Create from any 256-bit integer (little-endian limbs), reducing mod r
+    pub fn reduce(limbs: &[u64; 4]) -> Self {
+        // a * R^2 / R = a * R mod r; the Montgomery product of a < 2^256 and
+        // R^2 < r stays below 2r, so a single final subtraction suffices
+        Self::from_montgomery(*limbs).mul(&Self::from_montgomery(R2))
+    }
+
+    /// Create from canonical big-endian bytes
+    pub fn from_bytes_be(bytes: &[u8; 32]) -> Option<Self> {
+        let mut limbs = [0u64; 4];
+        for i in 0..4 {
+            let mut limb_bytes = [0u8; 8];
+            limb_bytes.copy_from_slice(&bytes[i * 8..i * 8 + 8]);
+            limbs[3 - i] = u64::from_be_bytes(limb_bytes);
+        }
+        Self::from_limbs(&limbs)
+    }
+
+    /// Create from canonical little-endian bytes
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn from_bytes_le(bytes: &[u8; 32]) -> Option<Self> {
+        let mut limbs = [0u64; 4];
+        for i in 0..4 {
+            let mut limb_bytes = [0u8; 8];
+            limb_bytes.copy_from_slice(&bytes[i * 8..i * 8 + 8]);
+            limbs[i] = u64::from_le_bytes(limb_bytes);
+        }
+        Self::from_limbs(&limbs)
+    }
+
+    /// Canonical integer representation (little-endian limbs)
+    pub fn to_limbs(self) -> [u64; 4] {
+        // Convert from Montgomery form
+        self.mul(&Fr::from_montgomery([1, 0, 0, 0])).limbs
+    }
+
+    /// Convert to bytes (big-endian)
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn to_bytes_be(self) -> [u8; 32] {
+        let limbs = self.to_limbs();
+        let mut bytes = [0u8; 32];
+        for i in 0..4 {
+            bytes[i * 8..i * 8 + 8].copy_from_slice(&limbs[3 - i].to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Convert to bytes (little-endian)
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn to_bytes_le(self) -> [u8; 32] {
+        let limbs = self.to_limbs();
+        let mut bytes = [0u8; 32];
+        for i in 0..4 {
+            bytes[i * 8..i * 8 + 8].copy_from_slice(&limbs[i].to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Zero element
+    pub const fn zero() -> Self {
+        Fr {
+            limbs: [0, 0, 0, 0],
+        }
+    }
+
+    /// One element (in Montgomery form)
+    pub const fn one() -> Self {
+        Fr::from_montgomery(R)
+    }
+
+    /// Check if zero
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn is_zero(&self) -> bool {
+        self.limbs == [0, 0, 0, 0]
+    }
+
+    /// Addition
+    pub fn add(&self, other: &Fr) -> Fr {
+        let mut result = [0u64; 4];
+        let mut carry = 0u128;
+
+        for i in 0..4 {
+            carry = carry + self.limbs[i] as u128 + other.limbs[i] as u128;
+            result[i] = carry as u64;
+            carry >>= 64;
+        }
+
+        // r < 2^254, so the sum never overflows 256 bits
+        Self::sub_modulus(&result)
+    }
+
+    /// Subtraction
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn sub(&self, other: &Fr) -> Fr {
+        let mut result = [0u64; 4];
+        let mut borrow = 0i128;
+
+        for i in 0..4 {
+            borrow = self.limbs[i] as i128 - other.limbs[i] as i128 - borrow;
+            result[i] = borrow as u64;
+            borrow = if borrow < 0 { 1 } else { 0 };
+        }
+
+        // Add modulus if result is negative
+        if borrow != 0 {
+            let mut carry = 0u128;
+            for i in 0..4 {
+                carry = carry + result[i] as u128 + MODULUS[i] as u128;
+                result[i] = carry as u64;
+                carry >>= 64;
+            }
+        }
+
+        Fr { limbs: result }
+    }
+
+    /// Negation
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn neg(&self) -> Fr {
+        Fr::zero().sub(self)
+    }
+
+    /// Montgomery multiplication (CIOS), see `Fq::mul`
+    pub fn mul(&self, other: &Fr) -> Fr {
+        let mut t = [0u64; 6];
+
+        for i in 0..4 {
+            let mut carry = 0u128;
+
+            // t += self * other.limbs[i]
+            for j in 0..4 {
+                carry += t[j] as u128 + (self.limbs[j] as u128) * (other.limbs[i] as u128);
+                t[j] = carry as u64;
+                carry >>= 64;
+            }
+            let acc = t[4] as u128 + carry;
+            t[4] = acc as u64;
+            t[5] = (acc >> 64) as u64;
+
+            // Montgomery reduction step: t = (t + k * r) / 2^64
+            let k = t[0].wrapping_mul(INV);
+            let mut carry = (t[0] as u128 + (k as u128) * (MODULUS[0] as u128)) >> 64;
+
+            for j in 1..4 {
+                carry += t[j] as u128 + (k as u128) * (MODULUS[j] as u128);
+                t[j - 1] = carry as u64;
+                carry >>= 64;
+            }
+            let acc = t[4] as u128 + carry;
+            t[3] = acc as u64;
+            t[4] = t[5] + (acc >> 64) as u64;
+        }
+
+        // r < 2^254, so the result is below 2r and t[4] is always zero here
+        Self::sub_modulus(&[t[0], t[1], t[2], t[3]])
+    }
+
+    /// Square
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn square(&self) -> Fr {
+        self.mul(self)
+    }
+
+    /// Power
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn pow(&self, exp: &[u64; 4]) -> Fr {
+        let mut result = Fr::one();
+        let mut base = *self;
+
+        for limb in exp.iter() {
+            for bit in 0..64 {
+                if (limb >> bit) & 1 == 1 {
+                    result = result.mul(&base);
+                }
+                base = base.square();
+            }
+        }
+
+        result
+    }
+
+    /// Multiplicative inverse using Fermat's little theorem
+    /// a^{-1} = a^{r-2} mod r
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub fn inverse(&self) -> Option<Fr> {
+        if self.is_zero() {
+            return None;
+        }
+
+        // Compute r - 2
+        let exp = [MODULUS[0] - 2, MODULUS[1], MODULUS[2], MODULUS[3]];
+
+        Some(self.pow(&exp))
+    }
+
+    /// Helper: subtract modulus if needed
+    fn sub_modulus(limbs: &[u64; 4]) -> Fr {
+        if limbs_less_than(limbs, &MODULUS) {
+            return Fr { limbs: *limbs };
+        }
+
+        let mut result = [0u64; 4];
+        let mut borrow = 0i128;
+
+        for i in 0..4 {
+            borrow = limbs[i] as i128 - MODULUS[i] as i128 - borrow;
+            result[i] = borrow as u64;
+            borrow = if borrow < 0 { 1 } else { 0 };
+        }
+
+        Fr { limbs: result }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// r + delta as big-endian bytes (delta in -1..=1)
+    fn modulus_plus_bytes(delta: i8) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for i in 0..4 {
+            bytes[i * 8..i * 8 + 8].copy_from_slice(&MODULUS[3 - i].to_be_bytes());
+        }
+        bytes[31] = bytes[31].wrapping_add_signed(delta);
+        bytes
+    }
+
+    #[test]
+    fn test_fr_small_values() {
+        let two = Fr::from_u64(2);
+        let three = Fr::from_u64(3);
+        assert_eq!(two.add(&three), Fr::from_u64(5));
+        assert_eq!(two.mul(&three), Fr::from_u64(6));
+        assert_eq!(two.sub(&three), Fr::one().neg());
+        assert_eq!(Fr::from_u64(1), Fr::one());
+        assert_eq!(Fr::from_u64(0), Fr::zero());
+    }
+
+    #[test]
+    fn test_fr_limbs_roundtrip_multi_limb() {
+        // Every limb populated, so a limb-order mistake cannot cancel out
+        let limbs = [
+            0x0123456789abcdef,
+            0xfedcba9876543210,
+            0x0f1e2d3c4b5a6978,
+            0x1020304050607080,
+        ];
+        let a = Fr::from_limbs(&limbs).unwrap();
+        assert_eq!(a.to_limbs(), limbs);
+
+        let be = a.to_bytes_be();
+        assert_eq!(be[0], 0x10);
+        assert_eq!(be[31], 0xef);
+        assert_eq!(Fr::from_bytes_be(&be), Some(a));
+
+        let le = a.to_bytes_le();
+        assert_eq!(le[0], 0xef);
+        assert_eq!(le[31], 0x10);
+        assert_eq!(Fr::from_bytes_le(&le), Some(a));
+    }
+
+    #[test]
+    fn test_fr_multi_limb_arithmetic() {
+        // 2^64 * 2^64 = 2^128
+        let two_64 = Fr::from_limbs(&[0, 1, 0, 0]).unwrap();
+        assert_eq!(two_64.mul(&two_64).to_limbs(), [0, 0, 1, 0]);
+
+        // (2^64 - 1) + 1 = 2^64 carries across limbs
+        let max_limb = Fr::from_u64(u64::MAX);
+        assert_eq!(max_limb.add(&Fr::one()), two_64);
+    }
+
+    #[test]
+    fn test_fr_rejects_non_canonical() {
+        let max = Fr::from_bytes_be(&modulus_plus_bytes(-1)).unwrap();
+        assert_eq!(max, Fr::one().neg());
+        assert_eq!(max.add(&Fr::one()), Fr::zero());
+
+        assert_eq!(Fr::from_bytes_be(&modulus_plus_bytes(0)), None);
+        assert_eq!(Fr::from_bytes_be(&modulus_plus_bytes(1)), None);
+        assert_eq!(Fr::from_limbs(&MODULUS), None);
+    }
+
+    #[test]
+    fn test_fr_reduce() {
+        // r reduces to 0, r + 1 to 1, 2^256 - 1 to (2^256 - 1) mod r
+        assert_eq!(Fr::reduce(&MODULUS), Fr::zero());
+        let r_plus_one = [MODULUS[0] + 1, MODULUS[1], MODULUS[2], MODULUS[3]];
+        assert_eq!(Fr::reduce(&r_plus_one), Fr::one());
+
+        let max = Fr::reduce(&[u64::MAX; 4]);
+        assert_eq!(
+            max.to_limbs(),
+            [
+                0xac96341c4ffffffa,
+                0x36fc76959f60cd29,
+                0x666ea36f7879462e,
+                0x0e0a77c19a07df2f,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fr_inverse() {
+        let a = Fr::from_limbs(&[7, 11, 13, 17]).unwrap();
+        let a_inv = a.inverse().unwrap();
+        assert_eq!(a.mul(&a_inv), Fr::one());
+        assert!(Fr::zero().inverse().is_none());
+    }
+}
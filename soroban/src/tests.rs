@@ -8,8 +8,13 @@
 
 #![cfg(test)]
 
+use crate::curve::{G1Affine, G2Affine};
+use crate::field::op_count;
+use crate::fq12::Fq12;
+use crate::scalar::Fr;
 use crate::*;
-use soroban_sdk::{Env, Bytes, Vec};
+use soroban_sdk::testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Ledger as _};
+use soroban_sdk::{xdr, Address, Bytes, BytesN, Env, IntoVal, Symbol, TryFromVal, Vec};
 
 // Test vector from actual snarkjs proof
 // These are real BN254 points from a valid Groth16 proof
@@ -37,16 +42,21 @@ fn get_real_g2_generator(env: &Env) -> G2Point {
     let mut y0_bytes = [0u8; 32];
     let mut y1_bytes = [0u8; 32];
 
-    // Real G2 generator coordinates (from BN254 spec)
-    // x = (11559732032986387107991004021392285783925812861821192530917403151452391805634,
-    //      10857046999023057135944570762232829481370756359578518086990519993285655852781)
-    // y = (4082367875863433681332203403145435568316851327593401208105741076214120093531,
-    //      8495653923123431417604973247489272438418190587263600148770280649306958101930)
-
-    let x0_hex: [u8; 32] = hex_to_bytes("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2");
-    let x1_hex: [u8; 32] = hex_to_bytes("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed");
-    let y0_hex: [u8; 32] = hex_to_bytes("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b");
-    let y1_hex: [u8; 32] = hex_to_bytes("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa");
+    // Real G2 generator coordinates (from BN254 spec), as [c0, c1] for c0 + c1*u.
+    // This matches snarkjs and zk_convert.js; EIP-197 calldata uses [c1, c0] instead.
+    // x = (10857046999023057135944570762232829481370756359578518086990519993285655852781,
+    //      11559732032986387107991004021392285783925812861821192530917403151452391805634)
+    // y = (8495653923123431417604973247489272438418190587263600148770280649306958101930,
+    //      4082367875863433681332203403145435568316851327593401208105741076214120093531)
+
+    let x0_hex: [u8; 32] =
+        hex_to_bytes("1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed");
+    let x1_hex: [u8; 32] =
+        hex_to_bytes("198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2");
+    let y0_hex: [u8; 32] =
+        hex_to_bytes("12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa");
+    let y1_hex: [u8; 32] =
+        hex_to_bytes("090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b");
 
     x0_bytes.copy_from_slice(&x0_hex);
     x1_bytes.copy_from_slice(&x1_hex);
@@ -72,6 +82,144 @@ fn hex_to_bytes(hex: &str) -> [u8; 32] {
     bytes
 }
 
+fn g1affine_to_point(env: &Env, point: &G1Affine) -> G1Point {
+    if point.is_infinity() {
+        return g1_zero_point(env);
+    }
+
+    G1Point {
+        x: Bytes::from_array(env, &point.x.to_bytes_be()),
+        y: Bytes::from_array(env, &point.y.to_bytes_be()),
+    }
+}
+
+fn g2affine_to_point(env: &Env, point: &G2Affine) -> G2Point {
+    if point.is_infinity() {
+        return g2_zero_point(env);
+    }
+
+    let mut x = Vec::new(env);
+    x.push_back(Bytes::from_array(env, &point.x.c0.to_bytes_be()));
+    x.push_back(Bytes::from_array(env, &point.x.c1.to_bytes_be()));
+
+    let mut y = Vec::new(env);
+    y.push_back(Bytes::from_array(env, &point.y.c0.to_bytes_be()));
+    y.push_back(Bytes::from_array(env, &point.y.c1.to_bytes_be()));
+
+    G2Point { x, y }
+}
+
+/// G2 point from big-endian hex coordinates [x.c0, x.c1, y.c0, y.c1]
+fn g2_point_from_hex(env: &Env, coords: [&str; 4]) -> G2Point {
+    let [x0, x1, y0, y1] = coords.map(|hex| Bytes::from_array(env, &hex_to_bytes(hex)));
+
+    let mut x = Vec::new(env);
+    x.push_back(x0);
+    x.push_back(x1);
+
+    let mut y = Vec::new(env);
+    y.push_back(y0);
+    y.push_back(y1);
+
+    G2Point { x, y }
+}
+
+fn scalars_to_bytes(env: &Env, scalars: &[Fr]) -> Vec<Bytes> {
+    let mut bytes = Vec::new(env);
+    for scalar in scalars {
+        bytes.push_back(Bytes::from_array(env, &scalar.to_bytes_be()));
+    }
+    bytes
+}
+
+/// Smallest Wasm module the host accepts as contract code: no functions,
+/// only the `contractenvmetav0` section with interface version 21
+const MINIMAL_WASM: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic, version 1
+    0x00, 0x1e, 0x11, // custom section: 30 bytes, 17-byte name
+    b'c', b'o', b'n', b't', b'r', b'a', b'c', b't', b'e', b'n', b'v', b'm', b'e', b't', b'a', b'v',
+    b'0', 0x00, 0x00, 0x00, 0x00, // SC_ENV_META_KIND_INTERFACE_VERSION
+    0x00, 0x00, 0x00, 0x15, 0x00, 0x00, 0x00, 0x00, // protocol 21, no pre-release
+];
+
+fn g1_zero_point(env: &Env) -> G1Point {
+    G1Point {
+        x: Bytes::from_array(env, &[0u8; 32]),
+        y: Bytes::from_array(env, &[0u8; 32]),
+    }
+}
+
+fn g2_zero_point(env: &Env) -> G2Point {
+    let mut x = Vec::new(env);
+    x.push_back(Bytes::from_array(env, &[0u8; 32]));
+    x.push_back(Bytes::from_array(env, &[0u8; 32]));
+
+    let mut y = Vec::new(env);
+    y.push_back(Bytes::from_array(env, &[0u8; 32]));
+    y.push_back(Bytes::from_array(env, &[0u8; 32]));
+
+    G2Point { x, y }
+}
+
+/// A point of the twist outside G2, with x = 2 + u
+fn non_subgroup_g2_point(env: &Env) -> G2Point {
+    g2_point_from_hex(
+        env,
+        [
+            "0000000000000000000000000000000000000000000000000000000000000002",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "101f7278419308b95099eca02dcee0c5381f4d26d1d62313f057167f064101ce",
+            "2b76c179599bb92a963dac85546a005a777f7c13f6a7b75d5918b6b5808f5fde",
+        ],
+    )
+}
+
+/// Build a verifying key and a valid proof from known discrete logs
+///
+/// With α = [a]G1, β = [b]G2, γ = [g]G2, δ = [d]G2, IC[i] = [k_i]G1,
+/// A = [x]G1 and B = [y]G2, the Groth16 equation holds for
+/// C = [(x·y - a·b - l·g) / d]G1 where l = k_0 + Σ k_i·s_i.
+fn synthetic_proof(
+    env: &Env,
+    ic_logs: &[Fr],
+    inputs: &[Fr],
+) -> (ProofData, VerifyingKey, Vec<Bytes>) {
+    let g1 = G1Affine::generator();
+    let g2 = G2Affine::generator();
+    let [a, b, g, d, x, y] = [11, 13, 17, 19, 23, 29].map(Fr::from_u64);
+
+    let mut l = ic_logs[0];
+    for (k, s) in ic_logs[1..].iter().zip(inputs.iter()) {
+        l = l.add(&k.mul(s));
+    }
+    let c = x
+        .mul(&y)
+        .sub(&a.mul(&b))
+        .sub(&l.mul(&g))
+        .mul(&d.inverse().unwrap());
+
+    let proof = ProofData {
+        pi_a: g1affine_to_point(env, &g1.mul(&x)),
+        pi_b: g2affine_to_point(env, &g2.mul(&y)),
+        pi_c: g1affine_to_point(env, &g1.mul(&c)),
+    };
+
+    let mut ic = Vec::new(env);
+    for k in ic_logs {
+        ic.push_back(g1affine_to_point(env, &g1.mul(k)));
+    }
+
+    let vk = VerifyingKey {
+        alpha: g1affine_to_point(env, &g1.mul(&a)),
+        beta: g2affine_to_point(env, &g2.mul(&b)),
+        gamma: g2affine_to_point(env, &g2.mul(&g)),
+        delta: g2affine_to_point(env, &g2.mul(&d)),
+        ic,
+    };
+
+    (proof, vk, scalars_to_bytes(env, inputs))
+}
+
 #[test]
 fn test_contract_version() {
     let env = Env::default();
@@ -79,7 +227,10 @@ fn test_contract_version() {
     let client = Groth16VerifierClient::new(&env, &contract_id);
 
     let version = client.version();
-    assert_eq!(version, 5, "Contract version should be 5 (with subgroup check)");
+    assert_eq!(
+        version, 7,
+        "Contract version should be 7 (with verify_proof_checked)"
+    );
 }
 
 #[test]
@@ -90,6 +241,7 @@ fn test_contract_info() {
 
     let info = client.info();
     assert_eq!(info.len(), 3, "Info should have 3 fields");
+    assert_eq!(info.get(2).unwrap(), Bytes::from_slice(&env, b"Version 7"));
 }
 
 #[test]
@@ -212,7 +364,7 @@ fn test_g1_infinity_point_is_on_curve() {
     };
 
     assert!(
-        Groth16Verifier::is_on_curve_g1(&env, &infinity),
+        validated::ic_point(&env, &infinity).unwrap().is_infinity(),
         "G1 infinity point should be on curve"
     );
 }
@@ -231,8 +383,11 @@ fn test_g2_infinity_point_is_on_curve() {
 
     let infinity = G2Point { x, y };
 
-    assert!(
-        Groth16Verifier::is_on_curve_g2(&env, &infinity),
+    let infinity = Groth16Verifier::bytes_to_g2affine(&env, &infinity).unwrap();
+    assert!(infinity.is_infinity());
+    assert_eq!(
+        validated::check_g2(&infinity),
+        Ok(()),
         "G2 infinity point should be on curve"
     );
 }
@@ -242,8 +397,9 @@ fn test_g1_generator_is_on_curve() {
     let env = Env::default();
     let g1_gen = get_real_g1_generator(&env);
 
-    assert!(
-        Groth16Verifier::is_on_curve_g1(&env, &g1_gen),
+    assert_eq!(
+        validated::ic_point(&env, &g1_gen),
+        Ok(G1Affine::generator()),
         "G1 generator should be on curve"
     );
 }
@@ -253,18 +409,116 @@ fn test_g2_generator_is_on_curve() {
     let env = Env::default();
     let g2_gen = get_real_g2_generator(&env);
 
-    assert!(
-        Groth16Verifier::is_on_curve_g2(&env, &g2_gen),
+    let g2_gen = Groth16Verifier::bytes_to_g2affine(&env, &g2_gen).unwrap();
+    assert_eq!(g2_gen, G2Affine::generator());
+    assert_eq!(
+        validated::check_g2(&g2_gen),
+        Ok(()),
         "G2 generator should be on curve and in correct subgroup"
     );
 }
 
+#[test]
+fn test_g2_off_curve_point_rejected() {
+    let env = Env::default();
+
+    // Generator with the Fq2 components of x swapped (EIP-197 ordering)
+    let mut point = get_real_g2_generator(&env);
+    let x0 = point.x.get(0).unwrap();
+    let x1 = point.x.get(1).unwrap();
+    point.x.set(0, x1);
+    point.x.set(1, x0);
+
+    let point = Groth16Verifier::bytes_to_g2affine(&env, &point).unwrap();
+    assert_eq!(
+        validated::check_g2(&point),
+        Err(VerifierError::NotOnCurve),
+        "G2 point with swapped x components should be rejected"
+    );
+}
+
+#[test]
+fn test_verify_proof_rejects_off_curve_vk_g2_points() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Groth16Verifier);
+    let client = Groth16VerifierClient::new(&env, &contract_id);
+
+    let proof = ProofData {
+        pi_a: get_real_g1_generator(&env),
+        pi_b: get_real_g2_generator(&env),
+        pi_c: get_real_g1_generator(&env),
+    };
+
+    let mut off_curve = get_real_g2_generator(&env);
+    off_curve.y.set(0, Bytes::from_array(&env, &[1u8; 32]));
+
+    let mut public_inputs = Vec::new(&env);
+    let mut input = [0u8; 32];
+    input[31] = 1;
+    public_inputs.push_back(Bytes::from_array(&env, &input));
+
+    for target in 0..3 {
+        let mut ic = Vec::new(&env);
+        ic.push_back(get_real_g1_generator(&env));
+        ic.push_back(get_real_g1_generator(&env));
+
+        let mut vk = VerifyingKey {
+            alpha: get_real_g1_generator(&env),
+            beta: get_real_g2_generator(&env),
+            gamma: get_real_g2_generator(&env),
+            delta: get_real_g2_generator(&env),
+            ic,
+        };
+        match target {
+            0 => vk.beta = off_curve.clone(),
+            1 => vk.gamma = off_curve.clone(),
+            _ => vk.delta = off_curve.clone(),
+        }
+
+        let result = client.verify_proof(&proof, &vk, &public_inputs);
+        assert!(!result, "VK with off-curve G2 point should be rejected");
+    }
+}
+
+#[test]
+fn test_verify_proof_rejects_off_curve_ic_points() {
+    let env = Env::default();
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+
+    // (1, 3) is not on the curve; the GLV split in the MSM would give a
+    // meaningless multiple of it, so it must be rejected up front
+    let off_curve = G1Point {
+        x: Bytes::from_array(&env, &Fq::one().to_bytes_be()),
+        y: Bytes::from_array(
+            &env,
+            &Fq::one().add(&Fq::one()).add(&Fq::one()).to_bytes_be(),
+        ),
+    };
+    for index in 0..2 {
+        let mut bad = vk.clone();
+        bad.ic.set(index, off_curve.clone());
+        assert_eq!(
+            Groth16Verifier::check_proof(&env, &proof, &bad, &public_inputs),
+            Err(VerifierError::NotOnCurve)
+        );
+    }
+
+    // The IC of a prepared key goes through the same decoding
+    let mut pvk = Groth16Verifier::prepare_vk(&env, &vk).unwrap();
+    pvk.ic.set(1, off_curve);
+    assert_eq!(
+        Groth16Verifier::check_proof_prepared(&env, &proof, &pvk, &public_inputs),
+        Err(VerifierError::NotOnCurve)
+    );
+}
+
 #[test]
 fn test_g1_point_negation() {
     let env = Env::default();
     let g1 = get_real_g1_generator(&env);
 
-    let neg_g1 = Groth16Verifier::g1_negate(&env, &g1);
+    let neg_g1 = g1affine_to_point(&env, &G1Affine::generator().neg());
 
     // x coordinate should stay the same
     assert_eq!(g1.x, neg_g1.x, "x coordinate should not change");
@@ -274,7 +528,7 @@ fn test_g1_point_negation() {
 
     // Negated point should still be on curve
     assert!(
-        Groth16Verifier::is_on_curve_g1(&env, &neg_g1),
+        validated::ic_point(&env, &neg_g1).is_ok(),
         "Negated point should be on curve"
     );
 }
@@ -285,12 +539,10 @@ fn test_g1_point_addition() {
     let g1 = get_real_g1_generator(&env);
 
     // Test: G + G = 2G
-    let result = Groth16Verifier::g1_add(&env, &g1, &g1);
-    assert!(result.is_some(), "G1 addition should succeed");
-
-    let doubled = result.unwrap();
+    let g = G1Affine::generator();
+    let doubled = g1affine_to_point(&env, &g.add(&g));
     assert!(
-        Groth16Verifier::is_on_curve_g1(&env, &doubled),
+        validated::ic_point(&env, &doubled).is_ok(),
         "Doubled point should be on curve"
     );
 
@@ -302,16 +554,11 @@ fn test_g1_point_addition() {
 fn test_g1_point_addition_with_infinity() {
     let env = Env::default();
     let g1 = get_real_g1_generator(&env);
-    let infinity = G1Point {
-        x: Bytes::from_array(&env, &[0u8; 32]),
-        y: Bytes::from_array(&env, &[0u8; 32]),
-    };
 
     // Test: G + 0 = G
-    let result = Groth16Verifier::g1_add(&env, &g1, &infinity);
-    assert!(result.is_some(), "G1 + infinity should succeed");
+    let g = G1Affine::generator();
+    let sum = g1affine_to_point(&env, &g.add(&G1Affine::infinity()));
 
-    let sum = result.unwrap();
     // Result should equal G (identity element)
     assert_eq!(g1.x, sum.x, "G + 0 should equal G (x coordinate)");
     assert_eq!(g1.y, sum.y, "G + 0 should equal G (y coordinate)");
@@ -323,15 +570,9 @@ fn test_g1_scalar_multiplication() {
     let g1 = get_real_g1_generator(&env);
 
     // Multiply by 2
-    let mut scalar = [0u8; 32];
-    scalar[31] = 2;
-
-    let result = Groth16Verifier::g1_scalar_mul(&env, &g1, &Bytes::from_array(&env, &scalar));
-    assert!(result.is_some(), "G1 scalar multiplication should succeed");
-
-    let doubled = result.unwrap();
+    let doubled = g1affine_to_point(&env, &G1Affine::generator().mul(&Fr::from_u64(2)));
     assert!(
-        Groth16Verifier::is_on_curve_g1(&env, &doubled),
+        validated::ic_point(&env, &doubled).is_ok(),
         "Result should be on curve"
     );
 
@@ -342,24 +583,16 @@ fn test_g1_scalar_multiplication() {
 #[test]
 fn test_g1_scalar_multiplication_by_zero() {
     let env = Env::default();
-    let g1 = get_real_g1_generator(&env);
 
     // Multiply by 0
-    let scalar = [0u8; 32];
-
-    let result = Groth16Verifier::g1_scalar_mul(&env, &g1, &Bytes::from_array(&env, &scalar));
-    assert!(result.is_some(), "G1 scalar multiplication by 0 should succeed");
-
-    let zero_point = result.unwrap();
-
-    // Result should be infinity point
-    assert!(
-        Groth16Verifier::is_zero_bytes(&zero_point.x),
-        "[0]G should be infinity (x = 0)"
-    );
-    assert!(
-        Groth16Verifier::is_zero_bytes(&zero_point.y),
-        "[0]G should be infinity (y = 0)"
+    let zero_point = G1Affine::generator().mul(&Fr::zero());
+
+    // Result should be infinity point, encoded as all zeros
+    assert!(zero_point.is_infinity(), "[0]G should be infinity");
+    assert_eq!(
+        g1affine_to_point(&env, &zero_point),
+        g1_zero_point(&env),
+        "[0]G should encode as (0, 0)"
     );
 }
 
@@ -371,19 +604,13 @@ fn test_linear_combination_single_input() {
     ic.push_back(get_real_g1_generator(&env)); // IC[0]
     ic.push_back(get_real_g1_generator(&env)); // IC[1]
 
-    let mut public_inputs = Vec::new(&env);
-    let mut input = [0u8; 32];
-    input[31] = 1; // public_input[0] = 1
-    public_inputs.push_back(Bytes::from_array(&env, &input));
+    let public_inputs = scalars_to_bytes(&env, &[Fr::from_u64(1)]); // public_input[0] = 1
 
-    let result = Groth16Verifier::compute_linear_combination(&env, &ic, &public_inputs);
-    assert!(result.is_some(), "Linear combination should succeed");
+    let result = validated::input_commitment(&env, &ic, &public_inputs);
+    assert!(result.is_ok(), "Linear combination should succeed");
 
     let vk_x = result.unwrap();
-    assert!(
-        Groth16Verifier::is_on_curve_g1(&env, &vk_x),
-        "Result should be on curve"
-    );
+    assert!(vk_x.is_on_curve(), "Result should be on curve");
 }
 
 #[test]
@@ -395,23 +622,13 @@ fn test_linear_combination_multiple_inputs() {
     ic.push_back(get_real_g1_generator(&env)); // IC[1]
     ic.push_back(get_real_g1_generator(&env)); // IC[2]
 
-    let mut public_inputs = Vec::new(&env);
-    let mut input1 = [0u8; 32];
-    input1[31] = 1;
-    public_inputs.push_back(Bytes::from_array(&env, &input1));
-
-    let mut input2 = [0u8; 32];
-    input2[31] = 2;
-    public_inputs.push_back(Bytes::from_array(&env, &input2));
+    let public_inputs = scalars_to_bytes(&env, &[Fr::from_u64(1), Fr::from_u64(2)]);
 
-    let result = Groth16Verifier::compute_linear_combination(&env, &ic, &public_inputs);
-    assert!(result.is_some(), "Linear combination should succeed");
+    let result = validated::input_commitment(&env, &ic, &public_inputs);
+    assert!(result.is_ok(), "Linear combination should succeed");
 
     let vk_x = result.unwrap();
-    assert!(
-        Groth16Verifier::is_on_curve_g1(&env, &vk_x),
-        "Result should be on curve"
-    );
+    assert!(vk_x.is_on_curve(), "Result should be on curve");
 }
 
 #[test]
@@ -445,19 +662,6 @@ fn test_verify_proof_wrong_public_input_length() {
     assert!(!result, "Proof with wrong public input length should fail");
 }
 
-#[test]
-fn test_is_zero_bytes() {
-    let env = Env::default();
-
-    let zero = Bytes::from_array(&env, &[0u8; 32]);
-    assert!(Groth16Verifier::is_zero_bytes(&zero), "All zeros should return true");
-
-    let mut non_zero = [0u8; 32];
-    non_zero[15] = 1;
-    let non_zero_bytes = Bytes::from_array(&env, &non_zero);
-    assert!(!Groth16Verifier::is_zero_bytes(&non_zero_bytes), "Non-zero should return false");
-}
-
 #[test]
 fn test_bytes_to_scalar_conversion() {
     let env = Env::default();
@@ -466,125 +670,2271 @@ fn test_bytes_to_scalar_conversion() {
     bytes[31] = 42;
 
     let scalar = Groth16Verifier::bytes_to_scalar(&Bytes::from_array(&env, &bytes));
-    assert!(scalar.is_some(), "Valid bytes should convert to scalar");
+    assert!(scalar.is_ok(), "Valid bytes should convert to scalar");
 
-    let scalar_array = scalar.unwrap();
+    let scalar_array = scalar.unwrap().to_limbs();
     assert_eq!(scalar_array[0], 42u64, "First limb should be 42");
 }
 
 #[test]
-fn test_bytes_to_scalar_wrong_length() {
+fn test_bytes_to_scalar_multi_limb() {
     let env = Env::default();
 
-    let bytes = Bytes::from_array(&env, &[0u8; 16]); // Wrong length
+    // 50 * 10^18: a kyc_transfer minBalance in 18-decimal token units,
+    // which no longer fits in a single limb
+    let bytes = hex_to_bytes("000000000000000000000000000000000000000000000002b5e3af16b1880000");
 
-    let scalar = Groth16Verifier::bytes_to_scalar(&bytes);
-    assert!(scalar.is_none(), "Wrong length should return None");
+    let scalar = Groth16Verifier::bytes_to_scalar(&Bytes::from_array(&env, &bytes)).unwrap();
+    assert_eq!(scalar.to_limbs(), [0xb5e3af16b1880000, 0x2, 0, 0]);
+    assert_eq!(scalar.to_bytes_be(), bytes);
 }
 
-// Security tests
-
 #[test]
-fn test_invalid_g1_point_rejected() {
+fn test_linear_combination_multi_limb_inputs() {
     let env = Env::default();
 
-    // Create a point not on the curve
-    let mut x = [0u8; 32];
-    x[31] = 1;
-    let mut y = [0u8; 32];
-    y[31] = 1; // (1, 1) is not on BN254 curve y² = x³ + 3
+    // IC = [G, G, 2G], inputs s1 = 50 * 10^18 and s2 = 2^128 + 18
+    let g = G1Affine::generator();
+    let mut ic = Vec::new(&env);
+    ic.push_back(get_real_g1_generator(&env));
+    ic.push_back(get_real_g1_generator(&env));
+    ic.push_back(g1affine_to_point(&env, &g.double()));
 
-    let invalid_point = G1Point {
-        x: Bytes::from_array(&env, &x),
-        y: Bytes::from_array(&env, &y),
+    let mut public_inputs = Vec::new(&env);
+    public_inputs.push_back(Bytes::from_array(
+        &env,
+        &hex_to_bytes("000000000000000000000000000000000000000000000002b5e3af16b1880000"),
+    ));
+    public_inputs.push_back(Bytes::from_array(
+        &env,
+        &hex_to_bytes("0000000000000000000000000000000100000000000000000000000000000012"),
+    ));
+    let vk_x = validated::input_commitment(&env, &ic, &public_inputs).unwrap();
+    let vk_x = g1affine_to_point(&env, &vk_x);
+
+    // [1 + s1 + 2 * s2]G, computed independently
+    let expected = G1Point {
+        x: Bytes::from_array(
+            &env,
+            &hex_to_bytes("2c14bf47c114a8a32d6fb4c4cf02142ee48929b04d62666915e15ce8cc4f789b"),
+        ),
+        y: Bytes::from_array(
+            &env,
+            &hex_to_bytes("194445e4b0072212965b265671523f7a7ec72852d5a92ca3f86af4c810c26302"),
+        ),
     };
+    assert_eq!(vk_x, expected);
+}
 
-    assert!(
-        !Groth16Verifier::is_on_curve_g1(&env, &invalid_point),
-        "Invalid G1 point should be rejected"
-    );
+#[test]
+fn test_linear_combination_matches_naive_loop() {
+    let env = Env::default();
+    let g = G1Affine::generator();
+
+    // 14 inputs as in kyc_transfer (Straus), and more than one MSM batch
+    // with a Pippenger-sized remainder
+    for n in [14u64, MSM_BATCH as u64 + 40] {
+        let mut ic = Vec::new(&env);
+        let mut naive = G1Affine::infinity();
+        let mut public_inputs = Vec::new(&env);
+        for i in 0..=n {
+            let point = g.mul(&Fr::from_u64(i * 31 + 7));
+            ic.push_back(g1affine_to_point(&env, &point));
+            if i == 0 {
+                naive = point;
+                continue;
+            }
+            // Full-width scalars: -1 / i mod r
+            let scalar = Fr::from_u64(i).inverse().unwrap().neg();
+            naive = naive.add(&point.mul(&scalar));
+            public_inputs.push_back(Bytes::from_array(&env, &scalar.to_bytes_be()));
+        }
+
+        let vk_x = validated::input_commitment(&env, &ic, &public_inputs).unwrap();
+        assert_eq!(vk_x, naive, "n = {}", n);
+    }
 }
 
 #[test]
-fn test_proof_verification_rejects_invalid_points() {
+fn test_bytes_to_scalar_wrong_length() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, Groth16Verifier);
-    let client = Groth16VerifierClient::new(&env, &contract_id);
 
-    // Create invalid proof with point not on curve
-    let mut x = [0u8; 32];
-    x[31] = 1;
-    let mut y = [0u8; 32];
-    y[31] = 1;
+    let bytes = Bytes::from_array(&env, &[0u8; 16]); // Wrong length
 
-    let invalid_pi_a = G1Point {
-        x: Bytes::from_array(&env, &x),
-        y: Bytes::from_array(&env, &y),
-    };
+    let scalar = Groth16Verifier::bytes_to_scalar(&bytes);
+    assert!(scalar.is_err(), "Wrong length should be rejected");
+}
+
+// Canonical encoding tests
+
+// Base field modulus p and scalar field modulus r, big-endian
+const FIELD_MODULUS_HEX: &str = "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+const SCALAR_MODULUS_HEX: &str = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+
+/// modulus + delta as big-endian bytes (the low byte of p and r absorbs ±1)
+fn modulus_offset_bytes(env: &Env, modulus_hex: &str, delta: i8) -> Bytes {
+    let mut bytes = hex_to_bytes(modulus_hex);
+    bytes[31] = bytes[31].wrapping_add_signed(delta);
+    Bytes::from_array(env, &bytes)
+}
 
+fn generator_proof_and_vk(env: &Env) -> (ProofData, VerifyingKey) {
     let proof = ProofData {
-        pi_a: invalid_pi_a,
-        pi_b: get_real_g2_generator(&env),
-        pi_c: get_real_g1_generator(&env),
+        pi_a: get_real_g1_generator(env),
+        pi_b: get_real_g2_generator(env),
+        pi_c: get_real_g1_generator(env),
     };
 
-    let mut ic = Vec::new(&env);
-    ic.push_back(get_real_g1_generator(&env));
-    ic.push_back(get_real_g1_generator(&env));
+    let mut ic = Vec::new(env);
+    ic.push_back(get_real_g1_generator(env));
+    ic.push_back(get_real_g1_generator(env));
 
     let vk = VerifyingKey {
-        alpha: get_real_g1_generator(&env),
-        beta: get_real_g2_generator(&env),
-        gamma: get_real_g2_generator(&env),
-        delta: get_real_g2_generator(&env),
+        alpha: get_real_g1_generator(env),
+        beta: get_real_g2_generator(env),
+        gamma: get_real_g2_generator(env),
+        delta: get_real_g2_generator(env),
         ic,
     };
 
-    let mut public_inputs = Vec::new(&env);
-    public_inputs.push_back(Bytes::from_array(&env, &[1u8; 32]));
-
-    let result = client.verify_proof(&proof, &vk, &public_inputs);
-    assert!(!result, "Proof with invalid point should be rejected");
+    (proof, vk)
 }
 
-// Performance/gas tests
+#[test]
+fn test_bytes_to_scalar_rejects_non_canonical() {
+    let env = Env::default();
+
+    let r_minus_one = modulus_offset_bytes(&env, SCALAR_MODULUS_HEX, -1);
+    assert!(Groth16Verifier::bytes_to_scalar(&r_minus_one).is_ok());
+
+    for delta in [0, 1] {
+        let scalar = modulus_offset_bytes(&env, SCALAR_MODULUS_HEX, delta);
+        assert_eq!(
+            Groth16Verifier::bytes_to_scalar(&scalar),
+            Err(VerifierError::NonCanonicalScalar),
+            "r + {} should be rejected",
+            delta
+        );
+    }
+}
 
 #[test]
-fn test_proof_verification_gas_usage() {
+fn test_verify_proof_rejects_non_canonical_g1_coordinate() {
     let env = Env::default();
-    env.budget().reset_default();
+    let (mut proof, vk) = generator_proof_and_vk(&env);
+
+    let mut public_inputs = Vec::new(&env);
+    public_inputs.push_back(Bytes::from_array(&env, &[0u8; 32]));
+
+    // p + 1 reduces to 1, so (p + 1, 2) would alias the G1 generator
+    proof.pi_a.x = modulus_offset_bytes(&env, FIELD_MODULUS_HEX, 1);
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof, &vk, &public_inputs),
+        Err(VerifierError::NonCanonicalFieldElement)
+    );
+
+    proof.pi_a.x = modulus_offset_bytes(&env, FIELD_MODULUS_HEX, 0);
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof, &vk, &public_inputs),
+        Err(VerifierError::NonCanonicalFieldElement)
+    );
+
+    // p - 1 is canonical; (p - 1, 2) is simply not on the curve
+    proof.pi_a.x = modulus_offset_bytes(&env, FIELD_MODULUS_HEX, -1);
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof, &vk, &public_inputs),
+        Err(VerifierError::NotOnCurve)
+    );
 
     let contract_id = env.register_contract(None, Groth16Verifier);
     let client = Groth16VerifierClient::new(&env, &contract_id);
+    proof.pi_a.x = modulus_offset_bytes(&env, FIELD_MODULUS_HEX, 1);
+    assert!(!client.verify_proof(&proof, &vk, &public_inputs));
+}
 
-    let proof = ProofData {
-        pi_a: get_real_g1_generator(&env),
-        pi_b: get_real_g2_generator(&env),
-        pi_c: get_real_g1_generator(&env),
-    };
+#[test]
+fn test_verify_proof_rejects_non_canonical_g2_coordinate() {
+    let env = Env::default();
+    let (proof, mut vk) = generator_proof_and_vk(&env);
 
-    let mut ic = Vec::new(&env);
-    ic.push_back(get_real_g1_generator(&env));
-    ic.push_back(get_real_g1_generator(&env));
+    let mut public_inputs = Vec::new(&env);
+    public_inputs.push_back(Bytes::from_array(&env, &[0u8; 32]));
 
-    let vk = VerifyingKey {
-        alpha: get_real_g1_generator(&env),
-        beta: get_real_g2_generator(&env),
-        gamma: get_real_g2_generator(&env),
-        delta: get_real_g2_generator(&env),
-        ic,
-    };
+    // Add p to the imaginary part of beta.y
+    let y1 = vk.beta.y.get(1).unwrap();
+    let mut y1_bytes = [0u8; 32];
+    y1.copy_into_slice(&mut y1_bytes);
+    let p_bytes = hex_to_bytes(FIELD_MODULUS_HEX);
+    let mut carry = 0u16;
+    for i in (0..32).rev() {
+        let sum = y1_bytes[i] as u16 + p_bytes[i] as u16 + carry;
+        y1_bytes[i] = sum as u8;
+        carry = sum >> 8;
+    }
+    assert_eq!(carry, 0);
+    vk.beta.y.set(1, Bytes::from_array(&env, &y1_bytes));
+
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof, &vk, &public_inputs),
+        Err(VerifierError::NonCanonicalFieldElement)
+    );
+}
+
+#[test]
+fn test_verify_proof_rejects_non_canonical_public_input() {
+    let env = Env::default();
+    let (proof, vk) = generator_proof_and_vk(&env);
+
+    for delta in [0, 1] {
+        let mut public_inputs = Vec::new(&env);
+        public_inputs.push_back(modulus_offset_bytes(&env, SCALAR_MODULUS_HEX, delta));
+        assert_eq!(
+            Groth16Verifier::check_proof(&env, &proof, &vk, &public_inputs),
+            Err(VerifierError::NonCanonicalScalar),
+            "public input r + {} should be rejected",
+            delta
+        );
+    }
 
+    // r - 1 is accepted as an input; this bogus proof then fails the pairing
     let mut public_inputs = Vec::new(&env);
-    let mut input = [0u8; 32];
-    input[31] = 1;
-    public_inputs.push_back(Bytes::from_array(&env, &input));
+    public_inputs.push_back(modulus_offset_bytes(&env, SCALAR_MODULUS_HEX, -1));
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof, &vk, &public_inputs),
+        Err(VerifierError::PairingCheckFailed)
+    );
+}
+
+// Point at infinity tests
+
+#[test]
+fn test_infinity_encoding_roundtrip() {
+    let env = Env::default();
+
+    let g1_inf = Groth16Verifier::bytes_to_g1affine(&env, &g1_zero_point(&env)).unwrap();
+    assert!(g1_inf.is_infinity());
+    assert_eq!(
+        g1affine_to_point(&env, &G1Affine::infinity()),
+        g1_zero_point(&env)
+    );
+
+    let g2_inf = Groth16Verifier::bytes_to_g2affine(&env, &g2_zero_point(&env)).unwrap();
+    assert!(g2_inf.is_infinity());
+
+    // Only the all-zero encoding means infinity
+    let mut half_zero = g1_zero_point(&env);
+    half_zero.y = get_real_g1_generator(&env).y;
+    let decoded = Groth16Verifier::bytes_to_g1affine(&env, &half_zero).unwrap();
+    assert!(!decoded.is_infinity());
+    assert_eq!(
+        validated::ic_point(&env, &half_zero),
+        Err(VerifierError::NotOnCurve)
+    );
+}
+
+#[test]
+fn test_synthetic_proof_verifies() {
+    let env = Env::default();
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof, &vk, &public_inputs),
+        Ok(())
+    );
+
+    let mut wrong_inputs = Vec::new(&env);
+    wrong_inputs.push_back(Bytes::from_array(&env, &Fr::from_u64(8).to_bytes_be()));
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof, &vk, &wrong_inputs),
+        Err(VerifierError::PairingCheckFailed)
+    );
+}
+
+#[test]
+fn test_verify_proof_zero_public_input() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Groth16Verifier);
+    let client = Groth16VerifierClient::new(&env, &contract_id);
 
-    // Execute verification
-    let _result = client.verify_proof(&proof, &vk, &public_inputs);
+    // [0]IC[1] is the identity and must drop out of L rather than add (0, 0)
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5), Fr::from_u64(6)];
+    let inputs = [Fr::zero(), Fr::from_u64(9)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &inputs);
+
+    assert!(client.verify_proof(&proof, &vk, &public_inputs));
+}
 
-    // Print budget usage
-    env.budget().print();
+#[test]
+fn test_verify_proof_identity_ic_points() {
+    let env = Env::default();
+
+    // IC[1] = O, and with IC[0] = O and a zero input L itself is O
+    let ic_logs = [Fr::zero(), Fr::zero(), Fr::from_u64(6)];
+    let (proof, vk, public_inputs) =
+        synthetic_proof(&env, &ic_logs, &[Fr::from_u64(4), Fr::zero()]);
+    assert_eq!(vk.ic.get(1).unwrap(), g1_zero_point(&env));
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof, &vk, &public_inputs),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_verify_proof_rejects_identity_proof_elements() {
+    let env = Env::default();
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+
+    let mut bad = proof.clone();
+    bad.pi_a = g1_zero_point(&env);
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &bad, &vk, &public_inputs),
+        Err(VerifierError::IdentityPoint)
+    );
+
+    let mut bad = proof.clone();
+    bad.pi_b = g2_zero_point(&env);
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &bad, &vk, &public_inputs),
+        Err(VerifierError::IdentityPoint)
+    );
+
+    let mut bad = proof;
+    bad.pi_c = g1_zero_point(&env);
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &bad, &vk, &public_inputs),
+        Err(VerifierError::IdentityPoint)
+    );
+}
+
+#[test]
+fn test_verify_proof_rejects_identity_vk_elements() {
+    let env = Env::default();
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+
+    let mut bad = vk.clone();
+    bad.alpha = g1_zero_point(&env);
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof, &bad, &public_inputs),
+        Err(VerifierError::IdentityPoint)
+    );
+
+    for i in 0..3 {
+        let mut bad = vk.clone();
+        match i {
+            0 => bad.beta = g2_zero_point(&env),
+            1 => bad.gamma = g2_zero_point(&env),
+            _ => bad.delta = g2_zero_point(&env),
+        }
+        assert_eq!(
+            Groth16Verifier::check_proof(&env, &proof, &bad, &public_inputs),
+            Err(VerifierError::IdentityPoint)
+        );
+    }
+}
+
+#[test]
+fn test_verify_proof_prepared_matches_verify_proof() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Groth16Verifier);
+    let client = Groth16VerifierClient::new(&env, &contract_id);
+
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5), Fr::from_u64(6)];
+    let inputs = [Fr::from_u64(7), Fr::zero()];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &inputs);
+
+    let pvk = client.prepare_verifying_key(&vk).unwrap();
+    assert_eq!(pvk.alpha_beta.len(), GT_BYTES);
+    assert_eq!(pvk.gamma_lines.len(), MILLER_LINE_COUNT as u32 * LINE_BYTES);
+    assert!(client.verify_proof(&proof, &vk, &public_inputs));
+    assert!(client.verify_proof_prepared(&proof, &pvk, &public_inputs));
+
+    let mut wrong_inputs = public_inputs.clone();
+    wrong_inputs.set(1, Bytes::from_array(&env, &Fr::from_u64(1).to_bytes_be()));
+    assert_eq!(
+        Groth16Verifier::check_proof_prepared(&env, &proof, &pvk, &wrong_inputs),
+        Err(VerifierError::PairingCheckFailed)
+    );
+
+    let mut bad = proof.clone();
+    bad.pi_c = g1_zero_point(&env);
+    assert_eq!(
+        Groth16Verifier::check_proof_prepared(&env, &bad, &pvk, &public_inputs),
+        Err(VerifierError::IdentityPoint)
+    );
+}
+
+#[test]
+fn test_prepare_verifying_key_rejects_invalid_keys() {
+    let env = Env::default();
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (_, vk, _) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+
+    let mut bad = vk.clone();
+    bad.gamma = g2_zero_point(&env);
+    assert_eq!(
+        Groth16Verifier::prepare_vk(&env, &bad),
+        Err(VerifierError::IdentityPoint)
+    );
+
+    // (1, 3) is not on the curve
+    let off_curve = G1Point {
+        x: Bytes::from_array(&env, &Fq::one().to_bytes_be()),
+        y: Bytes::from_array(
+            &env,
+            &Fq::one().add(&Fq::one()).add(&Fq::one()).to_bytes_be(),
+        ),
+    };
+    let mut bad = vk.clone();
+    bad.ic.set(1, off_curve);
+    assert_eq!(
+        Groth16Verifier::prepare_vk(&env, &bad),
+        Err(VerifierError::NotOnCurve)
+    );
+
+    let mut bad = vk;
+    bad.delta
+        .x
+        .set(0, modulus_offset_bytes(&env, FIELD_MODULUS_HEX, 0));
+    assert_eq!(
+        Groth16Verifier::prepare_vk(&env, &bad),
+        Err(VerifierError::NonCanonicalFieldElement)
+    );
+}
+
+#[test]
+fn test_verify_proof_prepared_rejects_malformed_lines() {
+    let env = Env::default();
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+    let pvk = Groth16Verifier::prepare_vk(&env, &vk).unwrap();
+
+    let mut bad = pvk.clone();
+    bad.gamma_lines = pvk.gamma_lines.slice(..pvk.gamma_lines.len() - LINE_BYTES);
+    assert_eq!(
+        Groth16Verifier::check_proof_prepared(&env, &proof, &bad, &public_inputs),
+        Err(VerifierError::MalformedVerifyingKey)
+    );
+
+    // Overwrite the last word of the last line with p
+    let mut bad = pvk.clone();
+    let end = bad.delta_lines.len();
+    let mut delta_lines = bad.delta_lines.slice(..end - 32);
+    delta_lines.append(&modulus_offset_bytes(&env, FIELD_MODULUS_HEX, 0));
+    bad.delta_lines = delta_lines;
+    assert_eq!(
+        Groth16Verifier::check_proof_prepared(&env, &proof, &bad, &public_inputs),
+        Err(VerifierError::NonCanonicalFieldElement)
+    );
+
+    // Lines of a different point decode fine but fail the equation
+    let mut bad = pvk.clone();
+    bad.delta_lines = pvk.gamma_lines.clone();
+    assert_eq!(
+        Groth16Verifier::check_proof_prepared(&env, &proof, &bad, &public_inputs),
+        Err(VerifierError::PairingCheckFailed)
+    );
+}
+
+#[test]
+fn test_verify_proof_prepared_rejects_malformed_alpha_beta() {
+    let env = Env::default();
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+    let pvk = Groth16Verifier::prepare_vk(&env, &vk).unwrap();
+
+    let mut bad = pvk.clone();
+    bad.alpha_beta = pvk.alpha_beta.slice(..GT_BYTES - 1);
+    assert_eq!(
+        Groth16Verifier::check_proof_prepared(&env, &proof, &bad, &public_inputs),
+        Err(VerifierError::MalformedVerifyingKey)
+    );
+
+    // c0.c0.c0 = p
+    let mut bad = pvk.clone();
+    let mut alpha_beta = modulus_offset_bytes(&env, FIELD_MODULUS_HEX, 0);
+    alpha_beta.append(&pvk.alpha_beta.slice(32..));
+    bad.alpha_beta = alpha_beta;
+    assert_eq!(
+        Groth16Verifier::check_proof_prepared(&env, &proof, &bad, &public_inputs),
+        Err(VerifierError::NonCanonicalFieldElement)
+    );
+
+    // e(α, β)^2 is a valid GT element, just not the right one
+    let mut bad = pvk.clone();
+    let squared = Groth16Verifier::bytes_to_fq12(&pvk.alpha_beta)
+        .unwrap()
+        .square();
+    bad.alpha_beta = Bytes::from_array(&env, &squared.to_bytes_be());
+    assert_eq!(
+        Groth16Verifier::check_proof_prepared(&env, &proof, &bad, &public_inputs),
+        Err(VerifierError::PairingCheckFailed)
+    );
+}
+
+#[test]
+fn test_prepared_alpha_beta_matches_snarkjs() {
+    // kyc_transfer_vkey.json: vk_alpha_1, vk_beta_2 and vk_alphabeta_12
+    let env = Env::default();
+    let alpha = G1Point {
+        x: Bytes::from_array(
+            &env,
+            &hex_to_bytes("17c3fd23a3b5338068e22be295fac0f88592d68e85a6aeaa8c0777fb92db7fd0"),
+        ),
+        y: Bytes::from_array(
+            &env,
+            &hex_to_bytes("0102f4ee08ef2ca7c8857d33c0e5b2ad00ea197683fb1192d02c73f2d5317af1"),
+        ),
+    };
+    let beta = g2_point_from_hex(
+        &env,
+        [
+            "046c42678a717c6edb3f2706289d735705e5f610d939a1ba2538a26893c64301",
+            "052736a437b24d88721506bb07ba6b68fb574e3e742b5bf25958f010cd940164",
+            "18ca48cad344c3a6e0d9edbefd5b1d94237025ac1539acc194c45dbf0fd6561b",
+            "3020e44ac03028bed142b7e79b0602b0552e73faa69c1c21db5c6e2a35cbe72c",
+        ],
+    );
+    let snarkjs_alpha_beta = [
+        "1849e9556448698c325bfa972dd5237e463bb1fb0e508b09e8791a875b2bbfb7",
+        "284012d17fe6a3225412b1ac78cf4c56fe86da71a5c0fcb18533e42c0dddc27b",
+        "1de839b85e88525fa8ce35a6cd254d764224daa330af39fec27839fdc53de646",
+        "057e8909128899e85b14cac41b0e46d4e21b7d48b7937c3b4315a361f4de2f6b",
+        "2f958b2fe090a6bf92381af2d5c5cc38cefdaac3c181f06472b96b3ce1250b1b",
+        "00a432ed90712c51973fda479cc63f2b550a31d15d943e848acfc97e23ba9424",
+        "21de4fd4f2bef9aacdf7df55ac68975ba7ed19d8caea7b64f1220cf84fbbe937",
+        "02be8193d57dbaa857872b293117b199c217316e1c7773b9f0e1ba0b60d727e4",
+        "19b56ed06921f057e14b8f7cb5f03edff480ef5f944ca03a43bce13671db3e66",
+        "12422343f6e49df2cfdcb5821a57f1e9f16ddfab8268e0c7991080692127bb08",
+        "221e8d60a6c4948786f863c57cebb4e5e5bbcf71bdb06a6c6e38ce2ca1c4696a",
+        "257a7866cf04909a01bf5d9b8e839033004eb344a4c46eb0ae3dd08898b4c138",
+    ];
+    let mut bytes = [0u8; 384];
+    for (chunk, hex) in bytes.chunks_exact_mut(32).zip(snarkjs_alpha_beta.iter()) {
+        chunk.copy_from_slice(&hex_to_bytes(hex));
+    }
+    let snarkjs = Fq12::from_bytes_be(&bytes).unwrap();
+
+    let alpha = Groth16Verifier::bytes_to_g1affine(&env, &alpha).unwrap();
+    let beta = Groth16Verifier::bytes_to_g2affine(&env, &beta).unwrap();
+    let ours = pairing::pairing(&alpha, &beta);
+
+    // snarkjs's final exponentiation raises to the same multiple of
+    // (p^12 - 1) / r, so its serialized value can be stored as is
+    assert_eq!(ours, snarkjs);
+    let pvk_alpha_beta = Bytes::from_array(&env, &ours.to_bytes_be());
+    assert_eq!(pvk_alpha_beta, Bytes::from_array(&env, &bytes));
+}
+
+#[test]
+fn test_verify_proof_prepared_saves_a_miller_loop() {
+    let env = Env::default();
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+    let pvk = Groth16Verifier::prepare_vk(&env, &vk).unwrap();
+
+    op_count::reset();
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof, &vk, &public_inputs),
+        Ok(())
+    );
+    let full_muls = op_count::fq_muls();
+
+    op_count::reset();
+    assert_eq!(
+        Groth16Verifier::check_proof_prepared(&env, &proof, &pvk, &public_inputs),
+        Ok(())
+    );
+    let prepared_muls = op_count::fq_muls();
+
+    // A single Miller loop for (α, β) is what the stored e(α, β) replaces
+    op_count::reset();
+    let _ = pairing::miller_loop(&G1Affine::generator(), &G2Affine::generator());
+    let miller_muls = op_count::fq_muls();
+
+    std::println!(
+        "verify: {} Fq muls, prepared: {}, one Miller loop: {}",
+        full_muls,
+        prepared_muls,
+        miller_muls
+    );
+    assert!(full_muls - prepared_muls >= miller_muls);
+}
+
+// Verifying-key registry tests
+
+/// A verifier with all auths mocked, initialized with a fresh admin
+fn initialized_verifier(env: &Env) -> (Groth16VerifierClient<'_>, Address) {
+    env.mock_all_auths();
+    let client = Groth16VerifierClient::new(env, &env.register_contract(None, Groth16Verifier));
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    (client, admin)
+}
+
+#[test]
+fn test_register_vk_and_verify() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5), Fr::from_u64(6)];
+    let inputs = [Fr::from_u64(7), Fr::from_u64(9)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &inputs);
+
+    let vk_id = client.register_vk(&admin, &vk);
+    assert_eq!(client.get_vk(&vk_id), Some(vk.clone()));
+    assert!(client.verify(&vk_id, &proof, &public_inputs));
+
+    let mut wrong_inputs = public_inputs.clone();
+    wrong_inputs.set(0, Bytes::from_array(&env, &Fr::from_u64(8).to_bytes_be()));
+    assert!(!client.verify(&vk_id, &proof, &wrong_inputs));
+
+    let unknown = BytesN::from_array(&env, &[7u8; 32]);
+    assert_eq!(client.get_vk(&unknown), None);
+    assert!(!client.verify(&unknown, &proof, &public_inputs));
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            Groth16Verifier::check_registered(&env, &unknown, &proof, &public_inputs),
+            Err(VerifierError::UnknownVerifyingKey)
+        );
+    });
+}
+
+#[test]
+fn test_register_vk_id_is_canonical() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let (other, other_admin) = initialized_verifier(&env);
+
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (_, vk, _) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+
+    // The ID depends only on the key: the same in every contract and on
+    // every registration
+    let vk_id = client.register_vk(&admin, &vk);
+    assert_eq!(client.register_vk(&admin, &vk), vk_id);
+    assert_eq!(other.register_vk(&other_admin, &vk), vk_id);
+
+    // Any other point gives another ID, and both keys stay registered
+    let mut changed = vk.clone();
+    changed
+        .ic
+        .set(1, g1affine_to_point(&env, &G1Affine::generator()));
+    let changed_id = client.register_vk(&admin, &changed);
+    assert_ne!(changed_id, vk_id);
+    assert_eq!(client.get_vk(&vk_id), Some(vk));
+    assert_eq!(client.get_vk(&changed_id), Some(changed));
+}
+
+#[test]
+fn test_register_vk_extends_entry_ttl() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let contract_id = client.address.clone();
+
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+    let vk_id = client.register_vk(&admin, &vk);
+
+    let ttls = || {
+        env.as_contract(&contract_id, || {
+            [
+                registry::DataKey::VerifyingKey(vk_id.clone()),
+                registry::DataKey::PreparedKey(vk_id.clone()),
+            ]
+            .map(|key| env.storage().persistent().get_ttl(&key))
+        })
+    };
+    let [vk_ttl, prepared_ttl] = ttls();
+    assert!(vk_ttl > 30 * 17_280);
+    assert_eq!(prepared_ttl, vk_ttl);
+
+    // Verifying against the key keeps it alive once it gets close to expiry
+    // (the contract instance itself is kept alive separately)
+    env.as_contract(&contract_id, || {
+        env.storage().instance().extend_ttl(vk_ttl, 2 * vk_ttl);
+    });
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number += vk_ttl - 1000);
+    assert!(client.verify(&vk_id, &proof, &public_inputs));
+    assert_eq!(ttls(), [vk_ttl, vk_ttl]);
+}
+
+#[test]
+fn test_register_vk_rejects_invalid_keys() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (_, vk, _) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+
+    // Identity, off the curve, and on the twist but outside G2
+    let mut identity = vk.clone();
+    identity.delta = g2_zero_point(&env);
+
+    let mut off_curve = vk.clone();
+    off_curve.ic.set(
+        0,
+        G1Point {
+            x: Bytes::from_array(&env, &Fq::one().to_bytes_be()),
+            y: Bytes::from_array(
+                &env,
+                &Fq::one().add(&Fq::one()).add(&Fq::one()).to_bytes_be(),
+            ),
+        },
+    );
+
+    let mut not_in_g2 = vk.clone();
+    not_in_g2.gamma = non_subgroup_g2_point(&env);
+
+    for (bad, error) in [
+        (identity, VerifierError::IdentityPoint),
+        (off_curve, VerifierError::NotOnCurve),
+        (not_in_g2, VerifierError::NotInSubgroup),
+    ] {
+        assert_eq!(client.try_register_vk(&admin, &bad), Err(Ok(error)));
+        let bad_id = env.as_contract(&client.address, || registry::key_id(&env, &bad));
+        assert_eq!(client.get_vk(&bad_id), None);
+    }
+}
+
+#[test]
+fn test_verify_checked_reports_each_failure() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5), Fr::from_u64(6)];
+    let inputs = [Fr::from_u64(7), Fr::from_u64(9)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &inputs);
+    let vk_id = client.register_vk(&admin, &vk);
+    client.verify_checked(&vk_id, &proof, &public_inputs);
+
+    let with_proof = |edit: &dyn Fn(&mut ProofData)| {
+        let mut bad = proof.clone();
+        edit(&mut bad);
+        (bad, public_inputs.clone())
+    };
+    let with_input = |input: Bytes| {
+        let mut bad = public_inputs.clone();
+        bad.set(0, input);
+        (proof.clone(), bad)
+    };
+    let off_curve = G1Point {
+        x: Bytes::from_array(&env, &Fq::one().to_bytes_be()),
+        y: Bytes::from_array(
+            &env,
+            &Fq::one().add(&Fq::one()).add(&Fq::one()).to_bytes_be(),
+        ),
+    };
+
+    let cases = [
+        (
+            with_proof(&|p| p.pi_a.x = Bytes::from_array(&env, &[1u8; 31])),
+            VerifierError::MalformedProof,
+        ),
+        (
+            with_input(Bytes::from_array(&env, &[1u8; 31])),
+            VerifierError::MalformedEncoding,
+        ),
+        (
+            (proof.clone(), public_inputs.slice(0..1)),
+            VerifierError::PublicInputCountMismatch,
+        ),
+        (
+            with_proof(&|p| p.pi_a.x = modulus_offset_bytes(&env, FIELD_MODULUS_HEX, 0)),
+            VerifierError::NonCanonicalFieldElement,
+        ),
+        (
+            with_input(modulus_offset_bytes(&env, SCALAR_MODULUS_HEX, 0)),
+            VerifierError::NonCanonicalScalar,
+        ),
+        (
+            with_proof(&|p| p.pi_c = g1_zero_point(&env)),
+            VerifierError::IdentityPoint,
+        ),
+        (
+            with_proof(&|p| p.pi_a = off_curve.clone()),
+            VerifierError::NotOnCurve,
+        ),
+        (
+            with_proof(&|p| p.pi_b = non_subgroup_g2_point(&env)),
+            VerifierError::NotInSubgroup,
+        ),
+        (
+            with_input(Bytes::from_array(&env, &Fr::from_u64(8).to_bytes_be())),
+            VerifierError::PairingCheckFailed,
+        ),
+    ];
+    for ((bad_proof, bad_inputs), error) in cases {
+        assert_eq!(
+            client.try_verify_checked(&vk_id, &bad_proof, &bad_inputs),
+            Err(Ok(error))
+        );
+        assert!(!client.verify(&vk_id, &bad_proof, &bad_inputs));
+    }
+
+    let unknown = BytesN::from_array(&env, &[7u8; 32]);
+    assert_eq!(
+        client.try_verify_checked(&unknown, &proof, &public_inputs),
+        Err(Ok(VerifierError::UnknownVerifyingKey))
+    );
+    client.deprecate_vk(&admin, &vk_id);
+    assert_eq!(
+        client.try_verify_checked(&vk_id, &proof, &public_inputs),
+        Err(Ok(VerifierError::DeprecatedVerifyingKey))
+    );
+}
+
+#[test]
+fn test_verify_proof_checked_reports_each_failure() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Groth16Verifier);
+    let client = Groth16VerifierClient::new(&env, &contract_id);
+
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5), Fr::from_u64(6)];
+    let inputs = [Fr::from_u64(7), Fr::from_u64(9)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &inputs);
+    client.verify_proof_checked(&proof, &vk, &public_inputs);
+    assert!(client.verify_proof(&proof, &vk, &public_inputs));
+
+    let with_proof = |edit: &dyn Fn(&mut ProofData)| {
+        let mut bad = proof.clone();
+        edit(&mut bad);
+        (bad, vk.clone(), public_inputs.clone())
+    };
+    let with_key = |edit: &dyn Fn(&mut VerifyingKey)| {
+        let mut bad = vk.clone();
+        edit(&mut bad);
+        (proof.clone(), bad, public_inputs.clone())
+    };
+    let with_input = |input: Bytes| {
+        let mut bad = public_inputs.clone();
+        bad.set(0, input);
+        (proof.clone(), vk.clone(), bad)
+    };
+    let off_curve = G1Point {
+        x: Bytes::from_array(&env, &Fq::one().to_bytes_be()),
+        y: Bytes::from_array(
+            &env,
+            &Fq::one().add(&Fq::one()).add(&Fq::one()).to_bytes_be(),
+        ),
+    };
+
+    let cases = [
+        (
+            with_proof(&|p| p.pi_a.x = Bytes::from_array(&env, &[1u8; 31])),
+            VerifierError::MalformedProof,
+        ),
+        (
+            with_key(&|k| k.alpha.x = Bytes::from_array(&env, &[1u8; 31])),
+            VerifierError::MalformedVerifyingKey,
+        ),
+        (
+            with_input(Bytes::from_array(&env, &[1u8; 31])),
+            VerifierError::MalformedEncoding,
+        ),
+        (
+            (proof.clone(), vk.clone(), public_inputs.slice(0..1)),
+            VerifierError::PublicInputCountMismatch,
+        ),
+        (
+            with_proof(&|p| p.pi_a.x = modulus_offset_bytes(&env, FIELD_MODULUS_HEX, 0)),
+            VerifierError::NonCanonicalFieldElement,
+        ),
+        (
+            with_input(modulus_offset_bytes(&env, SCALAR_MODULUS_HEX, 0)),
+            VerifierError::NonCanonicalScalar,
+        ),
+        (
+            with_key(&|k| k.gamma = g2_zero_point(&env)),
+            VerifierError::IdentityPoint,
+        ),
+        (
+            with_key(&|k| k.ic.set(1, off_curve.clone())),
+            VerifierError::NotOnCurve,
+        ),
+        (
+            with_key(&|k| k.delta = non_subgroup_g2_point(&env)),
+            VerifierError::NotInSubgroup,
+        ),
+        (
+            with_input(Bytes::from_array(&env, &Fr::from_u64(8).to_bytes_be())),
+            VerifierError::PairingCheckFailed,
+        ),
+    ];
+    for ((bad_proof, bad_vk, bad_inputs), error) in cases {
+        assert_eq!(
+            client.try_verify_proof_checked(&bad_proof, &bad_vk, &bad_inputs),
+            Err(Ok(error))
+        );
+        assert!(!client.verify_proof(&bad_proof, &bad_vk, &bad_inputs));
+    }
+}
+
+#[test]
+fn test_verifier_error_codes_are_stable() {
+    // Integrators match on these numbers: changing one breaks them
+    let codes = [
+        VerifierError::MalformedProof,
+        VerifierError::MalformedVerifyingKey,
+        VerifierError::MalformedEncoding,
+        VerifierError::PublicInputCountMismatch,
+        VerifierError::NonCanonicalFieldElement,
+        VerifierError::NonCanonicalScalar,
+        VerifierError::IdentityPoint,
+        VerifierError::NotOnCurve,
+        VerifierError::NotInSubgroup,
+        VerifierError::PairingCheckFailed,
+        VerifierError::UnknownVerifyingKey,
+        VerifierError::DeprecatedVerifyingKey,
+        VerifierError::AlreadyInitialized,
+        VerifierError::NotInitialized,
+        VerifierError::MissingRole,
+        VerifierError::NullifierAlreadyUsed,
+        VerifierError::InvalidNullifierInput,
+        VerifierError::NullifierInputAlreadySet,
+        VerifierError::BatchLengthMismatch,
+        VerifierError::BatchTooLarge,
+    ]
+    .map(|error| error as u32);
+    assert_eq!(codes, core::array::from_fn(|i| i as u32 + 1));
+}
+
+// Nullifier tests
+
+#[test]
+fn test_nullifier_input_prevents_replay() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let consumer = Address::generate(&env);
+    client.grant_role(&consumer, &Role::Consumer);
+
+    // The key only depends on the IC discrete logs, so each input vector
+    // below gets a proof for the same key
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5), Fr::from_u64(6)];
+    let (proof, vk, public_inputs) =
+        synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7), Fr::from_u64(100)]);
+    let vk_id = client.register_vk(&admin, &vk);
+    assert_eq!(client.nullifier_input(&vk_id), None);
+    client.set_nullifier_input(&admin, &vk_id, &1);
+    assert_eq!(client.nullifier_input(&vk_id), Some(1));
+
+    // Verifying only reads the record
+    let nullifier = BytesN::from_array(&env, &Fr::from_u64(100).to_bytes_be());
+    assert!(client.verify(&vk_id, &proof, &public_inputs));
+    assert_eq!(
+        client.try_verify_checked(&vk_id, &proof, &public_inputs),
+        Ok(Ok(()))
+    );
+    assert!(client.verify_logged(&vk_id, &proof, &public_inputs, &None));
+    assert!(!client.is_nullifier_used(&vk_id, &nullifier));
+
+    client.verify_and_consume(&consumer, &vk_id, &proof, &public_inputs);
+    assert_eq!(env.auths()[0].0, consumer);
+    assert!(client.is_nullifier_used(&vk_id, &nullifier));
+
+    // Replays fail, whatever the entrypoint and the other inputs
+    assert!(!client.verify(&vk_id, &proof, &public_inputs));
+    assert_eq!(
+        client.try_verify_checked(&vk_id, &proof, &public_inputs),
+        Err(Ok(VerifierError::NullifierAlreadyUsed))
+    );
+    assert_eq!(
+        client.try_verify_and_consume(&consumer, &vk_id, &proof, &public_inputs),
+        Err(Ok(VerifierError::NullifierAlreadyUsed))
+    );
+    assert!(!client.verify_logged(&vk_id, &proof, &public_inputs, &None));
+    let record = events::decode_event(&exported_events(&env).pop().unwrap()).unwrap();
+    assert_eq!(
+        record.error_code,
+        VerifierError::NullifierAlreadyUsed as u32
+    );
+    let (other_proof, _, other_inputs) =
+        synthetic_proof(&env, &ic_logs, &[Fr::from_u64(8), Fr::from_u64(100)]);
+    assert_eq!(
+        client.try_verify_checked(&vk_id, &other_proof, &other_inputs),
+        Err(Ok(VerifierError::NullifierAlreadyUsed))
+    );
+
+    // A rejected proof does not use up its nullifier
+    let (fresh_proof, _, fresh_inputs) =
+        synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7), Fr::from_u64(101)]);
+    let fresh = BytesN::from_array(&env, &Fr::from_u64(101).to_bytes_be());
+    assert_eq!(
+        client.try_verify_and_consume(&consumer, &vk_id, &proof, &fresh_inputs),
+        Err(Ok(VerifierError::PairingCheckFailed))
+    );
+    assert!(!client.is_nullifier_used(&vk_id, &fresh));
+    client.verify_and_consume(&consumer, &vk_id, &fresh_proof, &fresh_inputs);
+    assert!(client.is_nullifier_used(&vk_id, &fresh));
+
+    // Nullifiers are per key, and keys without a nullifier input accept
+    // the same proof any number of times
+    let (reused_proof, other_vk, reused_inputs) =
+        synthetic_proof(&env, &ic_logs[..2], &[Fr::from_u64(100)]);
+    let other_id = client.register_vk(&admin, &other_vk);
+    assert!(!client.is_nullifier_used(&other_id, &nullifier));
+    client.verify_and_consume(&consumer, &other_id, &reused_proof, &reused_inputs);
+    client.verify_and_consume(&consumer, &other_id, &reused_proof, &reused_inputs);
+}
+
+#[test]
+fn test_third_party_cannot_consume_a_nullifier() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let consumer = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    client.grant_role(&consumer, &Role::Consumer);
+
+    let (proof, vk, public_inputs) = synthetic_proof(
+        &env,
+        &[Fr::from_u64(3), Fr::from_u64(5)],
+        &[Fr::from_u64(7)],
+    );
+    let vk_id = client.register_vk(&admin, &vk);
+    client.set_nullifier_input(&admin, &vk_id, &0);
+    let nullifier = BytesN::from_array(&env, &Fr::from_u64(7).to_bytes_be());
+
+    // An attacker who copies the proof can neither consume it under their
+    // own name nor burn it through the read-only entrypoints
+    assert_eq!(
+        client.try_verify_and_consume(&attacker, &vk_id, &proof, &public_inputs),
+        Err(Ok(VerifierError::MissingRole))
+    );
+    assert!(client.verify(&vk_id, &proof, &public_inputs));
+    assert!(client.verify_logged(&vk_id, &proof, &public_inputs, &Some(attacker.clone())));
+    assert!(!client.is_nullifier_used(&vk_id, &nullifier));
+
+    // Nor in the consumer's name, without the consumer's authorization
+    env.set_auths(&[]);
+    assert!(client
+        .try_verify_and_consume(&consumer, &vk_id, &proof, &public_inputs)
+        .is_err());
+    assert!(!client.is_nullifier_used(&vk_id, &nullifier));
+
+    env.mock_all_auths();
+    client.verify_and_consume(&consumer, &vk_id, &proof, &public_inputs);
+    assert!(client.is_nullifier_used(&vk_id, &nullifier));
+}
+
+#[test]
+fn test_set_nullifier_input_rules() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5), Fr::from_u64(6)];
+    let (_, vk, _) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7), Fr::from_u64(9)]);
+    let vk_id = client.register_vk(&admin, &vk);
+
+    assert_eq!(
+        client.try_set_nullifier_input(&Address::generate(&env), &vk_id, &0),
+        Err(Ok(VerifierError::MissingRole))
+    );
+    assert_eq!(
+        client.try_set_nullifier_input(&admin, &BytesN::from_array(&env, &[7u8; 32]), &0),
+        Err(Ok(VerifierError::UnknownVerifyingKey))
+    );
+    assert_eq!(
+        client.try_set_nullifier_input(&admin, &vk_id, &2),
+        Err(Ok(VerifierError::InvalidNullifierInput))
+    );
+
+    client.set_nullifier_input(&admin, &vk_id, &0);
+    for index in [0, 1] {
+        assert_eq!(
+            client.try_set_nullifier_input(&admin, &vk_id, &index),
+            Err(Ok(VerifierError::NullifierInputAlreadySet))
+        );
+    }
+    assert_eq!(client.nullifier_input(&vk_id), Some(0));
+}
+
+#[test]
+fn test_nullifier_entries_get_maximum_ttl() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let contract_id = client.address.clone();
+    let (proof, vk, public_inputs) = synthetic_proof(
+        &env,
+        &[Fr::from_u64(3), Fr::from_u64(5)],
+        &[Fr::from_u64(7)],
+    );
+    let vk_id = client.register_vk(&admin, &vk);
+    client.set_nullifier_input(&admin, &vk_id, &0);
+
+    let nullifier = BytesN::from_array(&env, &Fr::from_u64(7).to_bytes_be());
+    assert!(!client.extend_nullifier_ttl(&vk_id, &nullifier));
+    client.verify_and_consume(&admin, &vk_id, &proof, &public_inputs);
+
+    let ttl = || {
+        env.as_contract(&contract_id, || {
+            let key = nullifier::NullifierKey::Spent(vk_id.clone(), nullifier.clone());
+            env.storage().persistent().get_ttl(&key)
+        })
+    };
+    let max_ttl = env.as_contract(&contract_id, || env.storage().max_ttl());
+    assert_eq!(ttl(), max_ttl);
+
+    // Anyone can push it back to the maximum (the instance is kept alive
+    // separately)
+    env.as_contract(&contract_id, || {
+        env.storage().instance().extend_ttl(max_ttl, max_ttl);
+    });
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number += 100_000);
+    assert_eq!(ttl(), max_ttl - 100_000);
+    env.set_auths(&[]);
+    assert!(client.extend_nullifier_ttl(&vk_id, &nullifier));
+    assert_eq!(ttl(), max_ttl);
+}
+
+// Batch verification tests
+
+/// `count` valid proofs for one key with a single public input, the
+/// key's ID, and the proofs with their input vectors
+fn registered_batch(
+    env: &Env,
+    client: &Groth16VerifierClient,
+    admin: &Address,
+    count: u64,
+) -> (BytesN<32>, Vec<ProofData>, Vec<Vec<Bytes>>) {
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let mut proofs = Vec::new(env);
+    let mut inputs = Vec::new(env);
+    let mut vk_id = None;
+    for i in 0..count {
+        let (proof, vk, public_inputs) = synthetic_proof(env, &ic_logs, &[Fr::from_u64(100 + i)]);
+        vk_id.get_or_insert_with(|| client.register_vk(admin, &vk));
+        proofs.push_back(proof);
+        inputs.push_back(public_inputs);
+    }
+    (vk_id.unwrap(), proofs, inputs)
+}
+
+#[test]
+fn test_verify_batch_accepts_valid_proofs() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let (vk_id, proofs, inputs) = registered_batch(&env, &client, &admin, 6);
+
+    let results = client.verify_batch(&vk_id, &proofs, &inputs);
+    assert_eq!(results, Vec::from_array(&env, [0u32; 6]));
+
+    // An empty batch is trivially accepted
+    let empty = client.verify_batch(&vk_id, &Vec::new(&env), &Vec::new(&env));
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_verify_batch_identifies_failing_proofs() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let (vk_id, mut proofs, mut inputs) = registered_batch(&env, &client, &admin, 6);
+
+    // 1: valid proof, wrong input; 2: identity A; 4: input count; 5: A and C
+    // swapped with another proof's
+    inputs.set(1, scalars_to_bytes(&env, &[Fr::from_u64(7)]));
+    let mut identity = proofs.get(2).unwrap();
+    identity.pi_a = g1_zero_point(&env);
+    proofs.set(2, identity);
+    inputs.set(4, Vec::new(&env));
+    let mut swapped = proofs.get(5).unwrap();
+    swapped.pi_c = proofs.get(0).unwrap().pi_c;
+    proofs.set(5, swapped);
+
+    let results = client.verify_batch(&vk_id, &proofs, &inputs);
+    let expected = [
+        0,
+        VerifierError::PairingCheckFailed as u32,
+        VerifierError::IdentityPoint as u32,
+        0,
+        VerifierError::PublicInputCountMismatch as u32,
+        VerifierError::PairingCheckFailed as u32,
+    ];
+    assert_eq!(results, Vec::from_array(&env, expected));
+
+    // Each code is the one verify_checked gives for that proof alone
+    for (i, code) in expected.iter().enumerate() {
+        let single = client.try_verify_checked(
+            &vk_id,
+            &proofs.get(i as u32).unwrap(),
+            &inputs.get(i as u32).unwrap(),
+        );
+        match single {
+            Ok(Ok(())) => assert_eq!(*code, 0),
+            Err(Ok(error)) => assert_eq!(*code, error as u32),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_verify_batch_rejects_malformed_batches() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let (vk_id, proofs, inputs) = registered_batch(&env, &client, &admin, 2);
+
+    let mut short_inputs = inputs.clone();
+    short_inputs.pop_back();
+    assert_eq!(
+        client.try_verify_batch(&vk_id, &proofs, &short_inputs),
+        Err(Ok(VerifierError::BatchLengthMismatch))
+    );
+
+    let mut many_proofs = Vec::new(&env);
+    let mut many_inputs = Vec::new(&env);
+    for _ in 0..=MAX_BATCH {
+        many_proofs.push_back(proofs.get(0).unwrap());
+        many_inputs.push_back(inputs.get(0).unwrap());
+    }
+    assert_eq!(
+        client.try_verify_batch(&vk_id, &many_proofs, &many_inputs),
+        Err(Ok(VerifierError::BatchTooLarge))
+    );
+
+    let unknown = BytesN::from_array(&env, &[7u8; 32]);
+    assert_eq!(
+        client.try_verify_batch(&unknown, &proofs, &inputs),
+        Err(Ok(VerifierError::UnknownVerifyingKey))
+    );
+    client.deprecate_vk(&admin, &vk_id);
+    assert_eq!(
+        client.try_verify_batch(&vk_id, &proofs, &inputs),
+        Err(Ok(VerifierError::DeprecatedVerifyingKey))
+    );
+}
+
+#[test]
+fn test_consume_batch_spends_nullifiers_in_order() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let consumer = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    client.grant_role(&consumer, &Role::Consumer);
+    let (vk_id, mut proofs, mut inputs) = registered_batch(&env, &client, &admin, 3);
+    client.set_nullifier_input(&admin, &vk_id, &0);
+
+    // 0: nullifier 100; 1: 100 again; 2: nullifier 102 with a bad proof;
+    // 3: 103
+    proofs.set(1, proofs.get(0).unwrap());
+    inputs.set(1, inputs.get(0).unwrap());
+    proofs.set(2, proofs.get(0).unwrap());
+    let (last_proof, _, last_inputs) = synthetic_proof(
+        &env,
+        &[Fr::from_u64(3), Fr::from_u64(5)],
+        &[Fr::from_u64(103)],
+    );
+    proofs.push_back(last_proof);
+    inputs.push_back(last_inputs);
+
+    let used = |value: u64| {
+        client.is_nullifier_used(
+            &vk_id,
+            &BytesN::from_array(&env, &Fr::from_u64(value).to_bytes_be()),
+        )
+    };
+    let pairing_failed = VerifierError::PairingCheckFailed as u32;
+
+    // verify_batch only reads nullifiers, and only consumers spend them
+    let results = client.verify_batch(&vk_id, &proofs, &inputs);
+    assert_eq!(results, Vec::from_array(&env, [0, 0, pairing_failed, 0]));
+    assert_eq!(
+        client.try_consume_batch(&attacker, &vk_id, &proofs, &inputs),
+        Err(Ok(VerifierError::MissingRole))
+    );
+    assert!(!used(100) && !used(103));
+
+    let results = client.consume_batch(&consumer, &vk_id, &proofs, &inputs);
+    assert_eq!(env.auths()[0].0, consumer);
+    let replayed = VerifierError::NullifierAlreadyUsed as u32;
+    assert_eq!(
+        results,
+        Vec::from_array(&env, [0, replayed, pairing_failed, 0])
+    );
+    assert!(used(100));
+    assert!(!used(102));
+    assert!(used(103));
+
+    // Spent nullifiers are rejected in later batches too
+    let replay = client.verify_batch(&vk_id, &proofs.slice(0..1), &inputs.slice(0..1));
+    assert_eq!(replay, Vec::from_array(&env, [replayed]));
+}
+
+#[test]
+fn test_verify_proof_batch_prepares_the_given_key() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let (vk_id, mut proofs, mut inputs) = registered_batch(&env, &client, &admin, 4);
+    let vk = client.get_vk(&vk_id).unwrap();
+
+    let results = client.verify_proof_batch(&vk, &proofs, &inputs);
+    assert_eq!(results, Vec::from_array(&env, [0u32; 4]));
+
+    // Same codes as against the registered key
+    inputs.set(1, scalars_to_bytes(&env, &[Fr::from_u64(7)]));
+    let mut identity = proofs.get(3).unwrap();
+    identity.pi_c = g1_zero_point(&env);
+    proofs.set(3, identity);
+    let results = client.verify_proof_batch(&vk, &proofs, &inputs);
+    assert_eq!(results, client.verify_batch(&vk_id, &proofs, &inputs));
+    let expected = [
+        0,
+        VerifierError::PairingCheckFailed as u32,
+        0,
+        VerifierError::IdentityPoint as u32,
+    ];
+    assert_eq!(results, Vec::from_array(&env, expected));
+
+    // A rejected key fails the whole call
+    let mut bad_vk = vk.clone();
+    bad_vk.delta = non_subgroup_g2_point(&env);
+    assert_eq!(
+        client.try_verify_proof_batch(&bad_vk, &proofs, &inputs),
+        Err(Ok(VerifierError::NotInSubgroup))
+    );
+    inputs.pop_back();
+    assert_eq!(
+        client.try_verify_proof_batch(&vk, &proofs, &inputs),
+        Err(Ok(VerifierError::BatchLengthMismatch))
+    );
+}
+
+#[test]
+fn test_verify_batch_shares_the_final_exponentiation() {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+    let (client, admin) = initialized_verifier(&env);
+    let count = 8;
+    let (vk_id, proofs, inputs) = registered_batch(&env, &client, &admin, count);
+
+    op_count::reset();
+    for (proof, public_inputs) in proofs.iter().zip(inputs.iter()) {
+        assert!(client.verify(&vk_id, &proof, &public_inputs));
+    }
+    let (separate_muls, separate_inversions) = (op_count::fq_muls(), op_count::fq_inversions());
+
+    op_count::reset();
+    let results = client.verify_batch(&vk_id, &proofs, &inputs);
+    assert_eq!(results, Vec::from_array(&env, [0u32; 8]));
+    let (batch_muls, batch_inversions) = (op_count::fq_muls(), op_count::fq_inversions());
+
+    std::println!(
+        "{} proofs: {} Fq muls, {} inversions separately; {} muls, {} inversions batched",
+        count,
+        separate_muls,
+        separate_inversions,
+        batch_muls,
+        batch_inversions
+    );
+
+    // n full Miller loops (one inversion per line) and no more
+    let lines = pairing::MILLER_LINE_COUNT as u64;
+    assert!(batch_inversions >= count * lines);
+    assert!(batch_inversions < (count + 1) * lines);
+
+    // The inputs of all proofs are folded into one MSM over IC, so per
+    // proof the batch only inverts for its Miller lines and for the affine
+    // P + φ(P) that `mul_glv` takes to scale A_i; the rest is shared
+    op_count::reset();
+    let single = client.verify_batch(&vk_id, &proofs.slice(0..1), &inputs.slice(0..1));
+    assert_eq!(single, Vec::from_array(&env, [0u32]));
+    let per_proof = lines + 1;
+    assert_eq!(
+        batch_inversions - count * per_proof,
+        op_count::fq_inversions() - per_proof
+    );
+
+    // At least n - 1 final exponentiations are saved
+    let f = pairing::miller_loop(&G1Affine::generator(), &G2Affine::generator());
+    op_count::reset();
+    let _ = pairing::final_exponentiation(&f);
+    let final_exp_muls = op_count::fq_muls();
+    assert!(separate_muls - batch_muls >= (count - 1) * final_exp_muls);
+}
+
+// Verification event tests
+
+fn sc_address(env: &Env, address: &Address) -> xdr::ScAddress {
+    match xdr::ScVal::try_from_val(env, &address.to_val()).unwrap() {
+        xdr::ScVal::Address(address) => address,
+        _ => panic!("addresses convert to ScVal::Address"),
+    }
+}
+
+/// Contract events published so far as they appear in transaction
+/// metadata, passed through their base64 XDR export
+fn exported_events(env: &Env) -> std::vec::Vec<xdr::ContractEvent> {
+    use soroban_sdk::testutils::Events as _;
+    use xdr::{ReadXdr, WriteXdr};
+
+    env.events()
+        .all()
+        .iter()
+        .map(|(contract, topics, data)| {
+            let xdr::ScAddress::Contract(contract_id) = sc_address(env, &contract) else {
+                panic!("events are published by contracts");
+            };
+            let topics: std::vec::Vec<xdr::ScVal> = topics
+                .iter()
+                .map(|topic| xdr::ScVal::try_from_val(env, &topic).unwrap())
+                .collect();
+            let event = xdr::ContractEvent {
+                ext: xdr::ExtensionPoint::V0,
+                contract_id: Some(contract_id),
+                type_: xdr::ContractEventType::Contract,
+                body: xdr::ContractEventBody::V0(xdr::ContractEventV0 {
+                    topics: topics.try_into().unwrap(),
+                    data: xdr::ScVal::try_from_val(env, &data).unwrap(),
+                }),
+            };
+            let exported = event.to_xdr_base64(xdr::Limits::none()).unwrap();
+            xdr::ContractEvent::from_xdr_base64(exported, xdr::Limits::none()).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn test_verify_logged_publishes_decodable_events() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let xdr::ScAddress::Contract(xdr::Hash(contract_id)) = sc_address(&env, &client.address) else {
+        panic!("the verifier is a contract");
+    };
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number = 1234);
+
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5), Fr::from_u64(6)];
+    let inputs = [Fr::from_u64(7), Fr::from_u64(9)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &inputs);
+    let vk_id = client.register_vk(&admin, &vk);
+
+    let mut concatenated = [0u8; 64];
+    concatenated[..32].copy_from_slice(&inputs[0].to_bytes_be());
+    concatenated[32..].copy_from_slice(&inputs[1].to_bytes_be());
+    let inputs_hash: [u8; 32] = env
+        .crypto()
+        .sha256(&Bytes::from_array(&env, &concatenated))
+        .to_array();
+
+    // Accepted, with an invoker who has to sign
+    let invoker = Address::generate(&env);
+    assert!(client.verify_logged(&vk_id, &proof, &public_inputs, &Some(invoker.clone())));
+    assert_eq!(env.auths()[0].0, invoker);
+    let events = exported_events(&env);
+    assert_eq!(events.len(), 1);
+    assert_eq!(
+        events::decode_event(&events[0]),
+        Some(events::AuditRecord {
+            contract_id,
+            vk_id: vk_id.to_array(),
+            verified: true,
+            error_code: 0,
+            inputs_hash,
+            invoker: Some(sc_address(&env, &invoker)),
+            ledger: 1234,
+        })
+    );
+
+    // Rejected: the call still succeeds, so the event is kept
+    let mut wrong_inputs = public_inputs.clone();
+    wrong_inputs.set(0, Bytes::from_array(&env, &Fr::from_u64(8).to_bytes_be()));
+    assert!(!client.verify_logged(&vk_id, &proof, &wrong_inputs, &None));
+    let record = events::decode_event(&exported_events(&env).pop().unwrap()).unwrap();
+    assert!(!record.verified);
+    assert_eq!(record.error_code, VerifierError::PairingCheckFailed as u32);
+    assert_ne!(record.inputs_hash, inputs_hash);
+    assert_eq!(record.invoker, None);
+
+    let unknown = BytesN::from_array(&env, &[7u8; 32]);
+    assert!(!client.verify_logged(&unknown, &proof, &public_inputs, &None));
+    let record = events::decode_event(&exported_events(&env).pop().unwrap()).unwrap();
+    assert_eq!(record.vk_id, [7u8; 32]);
+    assert_eq!(record.error_code, VerifierError::UnknownVerifyingKey as u32);
+    assert_eq!(record.inputs_hash, inputs_hash);
+}
+
+#[test]
+fn test_decode_event_skips_other_events() {
+    let env = Env::default();
+    let (client, _) = initialized_verifier(&env);
+    let (proof, _, public_inputs) = synthetic_proof(&env, &[Fr::from_u64(3)], &[]);
+    client.verify_logged(
+        &BytesN::from_array(&env, &[7u8; 32]),
+        &proof,
+        &public_inputs,
+        &None,
+    );
+    let event = exported_events(&env).pop().unwrap();
+    assert!(events::decode_event(&event).is_some());
+
+    let xdr::ContractEventBody::V0(body) = &event.body;
+    let mut other_topic = event.clone();
+    let mut topics = body.topics.to_vec();
+    topics[0] = xdr::ScVal::Symbol(xdr::ScSymbol("transfer".try_into().unwrap()));
+    other_topic.body = xdr::ContractEventBody::V0(xdr::ContractEventV0 {
+        topics: topics.try_into().unwrap(),
+        data: body.data.clone(),
+    });
+    assert_eq!(events::decode_event(&other_topic), None);
+
+    let mut other_data = event.clone();
+    other_data.body = xdr::ContractEventBody::V0(xdr::ContractEventV0 {
+        topics: body.topics.clone(),
+        data: xdr::ScVal::U32(1),
+    });
+    assert_eq!(events::decode_event(&other_data), None);
+
+    let mut system = event;
+    system.type_ = xdr::ContractEventType::System;
+    assert_eq!(events::decode_event(&system), None);
+}
+
+// Admin and role tests
+
+#[test]
+fn test_initialize_sets_admin_once() {
+    let env = Env::default();
+    let client = Groth16VerifierClient::new(&env, &env.register_contract(None, Groth16Verifier));
+    let admin = Address::generate(&env);
+    assert_eq!(client.admin(), None);
+
+    env.mock_all_auths();
+    client.initialize(&admin);
+    assert_eq!(
+        env.auths(),
+        std::vec![(
+            admin.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    client.address.clone(),
+                    Symbol::new(&env, "initialize"),
+                    (admin.clone(),).into_val(&env),
+                )),
+                sub_invocations: std::vec![],
+            }
+        )]
+    );
+    assert_eq!(client.admin(), Some(admin));
+
+    assert_eq!(
+        client.try_initialize(&Address::generate(&env)),
+        Err(Ok(VerifierError::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_upgrade_requires_admin() {
+    let env = Env::default();
+    let client = Groth16VerifierClient::new(&env, &env.register_contract(None, Groth16Verifier));
+    let wasm_hash = env.deployer().upload_contract_wasm(MINIMAL_WASM);
+
+    env.mock_all_auths();
+    assert_eq!(
+        client.try_upgrade(&wasm_hash),
+        Err(Ok(VerifierError::NotInitialized))
+    );
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    env.set_auths(&[]);
+    assert!(
+        client.try_upgrade(&wasm_hash).is_err(),
+        "admin did not authorize"
+    );
+
+    env.mock_all_auths();
+    client.upgrade(&wasm_hash);
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, admin);
+
+    // The new code has none of the old entrypoints
+    assert!(client.try_version().is_err());
+}
+
+#[test]
+fn test_set_admin_requires_both_admins() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let new_admin = Address::generate(&env);
+
+    client.set_admin(&new_admin);
+    let signers: std::vec::Vec<Address> = env.auths().into_iter().map(|(a, _)| a).collect();
+    assert_eq!(signers, [admin.clone(), new_admin.clone()]);
+    assert_eq!(client.admin(), Some(new_admin.clone()));
+
+    // Admin-only entrypoints now follow the new admin
+    let account = Address::generate(&env);
+    client.grant_role(&account, &Role::Registrar);
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert!(!client.has_role(&admin, &Role::Registrar));
+    assert!(client.has_role(&new_admin, &Role::Registrar));
+
+    env.set_auths(&[]);
+    assert!(client.try_grant_role(&account, &Role::Deprecator).is_err());
+    assert!(client.try_set_admin(&admin).is_err());
+}
+
+#[test]
+fn test_roles_gate_key_registry() {
+    let env = Env::default();
+    let (client, admin) = initialized_verifier(&env);
+    let registrar = Address::generate(&env);
+    let deprecator = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+
+    client.grant_role(&registrar, &Role::Registrar);
+    client.grant_role(&deprecator, &Role::Deprecator);
+    assert!(client.has_role(&admin, &Role::Deprecator));
+    assert!(!client.has_role(&registrar, &Role::Deprecator));
+
+    // Registering: the registrar signs, outsiders and deprecators are refused
+    let missing_role = Err(Ok(VerifierError::MissingRole));
+    assert_eq!(client.try_register_vk(&outsider, &vk), missing_role);
+    assert_eq!(client.try_register_vk(&deprecator, &vk), missing_role);
+    let vk_id = client.register_vk(&registrar, &vk);
+    assert_eq!(env.auths()[0].0, registrar);
+    assert!(client.verify(&vk_id, &proof, &public_inputs));
+
+    // Deprecating: the key stays readable but no longer verifies, also
+    // after registering it again
+    assert_eq!(
+        client.try_deprecate_vk(&registrar, &vk_id),
+        Err(Ok(VerifierError::MissingRole))
+    );
+    assert_eq!(
+        client.try_deprecate_vk(&deprecator, &BytesN::from_array(&env, &[7u8; 32])),
+        Err(Ok(VerifierError::UnknownVerifyingKey))
+    );
+    assert!(!client.is_vk_deprecated(&vk_id));
+    client.deprecate_vk(&deprecator, &vk_id);
+    assert_eq!(env.auths()[0].0, deprecator);
+    assert!(client.is_vk_deprecated(&vk_id));
+    assert_eq!(client.get_vk(&vk_id), Some(vk.clone()));
+    assert_eq!(client.register_vk(&registrar, &vk), vk_id);
+    assert!(!client.verify(&vk_id, &proof, &public_inputs));
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            Groth16Verifier::check_registered(&env, &vk_id, &proof, &public_inputs),
+            Err(VerifierError::DeprecatedVerifyingKey)
+        );
+    });
+
+    client.revoke_role(&registrar, &Role::Registrar);
+    assert!(!client.has_role(&registrar, &Role::Registrar));
+    assert_eq!(
+        client.try_register_vk(&registrar, &vk),
+        Err(Ok(VerifierError::MissingRole))
+    );
+}
+
+#[test]
+fn test_register_vk_requires_initialization() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let client = Groth16VerifierClient::new(&env, &env.register_contract(None, Groth16Verifier));
+    let (_, vk, _) = synthetic_proof(&env, &[Fr::from_u64(3)], &[]);
+
+    assert_eq!(
+        client.try_register_vk(&Address::generate(&env), &vk),
+        Err(Ok(VerifierError::MissingRole))
+    );
+}
+
+#[test]
+fn test_grants_are_persistent_and_calls_keep_the_instance_alive() {
+    use soroban_sdk::testutils::storage::{Instance as _, Persistent as _};
+
+    let env = Env::default();
+    let (client, _) = initialized_verifier(&env);
+    let registrar = Address::generate(&env);
+    client.grant_role(&registrar, &Role::Registrar);
+
+    let grant = access::AccessKey::Grant(registrar.clone(), Role::Registrar);
+    let ttls = || {
+        env.as_contract(&client.address, || {
+            assert!(!env.storage().instance().has(&grant));
+            (
+                env.storage().instance().get_ttl(),
+                env.storage().persistent().get_ttl(&grant),
+            )
+        })
+    };
+    assert_eq!(ttls(), (access::TTL_EXTEND_TO, access::TTL_EXTEND_TO));
+
+    // Once below the threshold, any call extends the instance, and checking
+    // a grant extends it too
+    let elapsed = access::TTL_EXTEND_TO - access::TTL_THRESHOLD + 1;
+    env.ledger()
+        .with_mut(|ledger| ledger.sequence_number += elapsed);
+    assert_eq!(
+        ttls(),
+        (access::TTL_THRESHOLD - 1, access::TTL_THRESHOLD - 1)
+    );
+    assert_eq!(client.version(), CONTRACT_VERSION);
+    assert_eq!(ttls().0, access::TTL_EXTEND_TO);
+    assert!(client.has_role(&registrar, &Role::Registrar));
+    assert_eq!(ttls(), (access::TTL_EXTEND_TO, access::TTL_EXTEND_TO));
+}
+
+// Security tests
+
+#[test]
+fn test_invalid_g1_point_rejected() {
+    let env = Env::default();
+
+    // Create a point not on the curve
+    let mut x = [0u8; 32];
+    x[31] = 1;
+    let mut y = [0u8; 32];
+    y[31] = 1; // (1, 1) is not on BN254 curve y² = x³ + 3
+
+    let invalid_point = G1Point {
+        x: Bytes::from_array(&env, &x),
+        y: Bytes::from_array(&env, &y),
+    };
+
+    assert_eq!(
+        validated::ic_point(&env, &invalid_point),
+        Err(VerifierError::NotOnCurve),
+        "Invalid G1 point should be rejected"
+    );
+}
+
+#[test]
+fn test_proof_verification_rejects_invalid_points() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Groth16Verifier);
+    let client = Groth16VerifierClient::new(&env, &contract_id);
+
+    // Create invalid proof with point not on curve
+    let mut x = [0u8; 32];
+    x[31] = 1;
+    let mut y = [0u8; 32];
+    y[31] = 1;
+
+    let invalid_pi_a = G1Point {
+        x: Bytes::from_array(&env, &x),
+        y: Bytes::from_array(&env, &y),
+    };
+
+    let proof = ProofData {
+        pi_a: invalid_pi_a,
+        pi_b: get_real_g2_generator(&env),
+        pi_c: get_real_g1_generator(&env),
+    };
+
+    let mut ic = Vec::new(&env);
+    ic.push_back(get_real_g1_generator(&env));
+    ic.push_back(get_real_g1_generator(&env));
+
+    let vk = VerifyingKey {
+        alpha: get_real_g1_generator(&env),
+        beta: get_real_g2_generator(&env),
+        gamma: get_real_g2_generator(&env),
+        delta: get_real_g2_generator(&env),
+        ic,
+    };
+
+    let mut public_inputs = Vec::new(&env);
+    public_inputs.push_back(Bytes::from_array(&env, &[1u8; 32]));
+
+    let result = client.verify_proof(&proof, &vk, &public_inputs);
+    assert!(!result, "Proof with invalid point should be rejected");
+}
+
+// Performance/gas tests
+
+/// Field operations of one verification stage
+///
+/// The host budget does not meter contract code in native test builds, so
+/// here the stages are measured by their field arithmetic, counted with
+/// `op_count`. `WASM_STAGE_BUDGETS` bounds their metered cost.
+#[derive(Clone, Copy, Debug)]
+struct StageCost {
+    fq_muls: u64,
+    fq_squares: u64,
+    fq_inversions: u64,
+}
+
+impl StageCost {
+    const fn new(fq_muls: u64, fq_squares: u64, fq_inversions: u64) -> Self {
+        StageCost {
+            fq_muls,
+            fq_squares,
+            fq_inversions,
+        }
+    }
+
+    fn fields(&self) -> [(&'static str, u64); 3] {
+        [
+            ("Fq muls", self.fq_muls),
+            ("Fq squarings", self.fq_squares),
+            ("Fq inversions", self.fq_inversions),
+        ]
+    }
+
+    fn sub(&self, other: &Self) -> Self {
+        StageCost::new(
+            self.fq_muls - other.fq_muls,
+            self.fq_squares - other.fq_squares,
+            self.fq_inversions - other.fq_inversions,
+        )
+    }
+}
+
+const STAGES: [&str; 5] = [
+    "decode",
+    "subgroup checks",
+    "msm",
+    "miller loop",
+    "final exponentiation",
+];
+
+/// Checked-in upper bounds per number of public inputs, in `STAGES` order
+///
+/// Each bound is the measured count plus about 10%, so a regression fails
+/// the test; lower them along with any change that makes a stage cheaper.
+const STAGE_BUDGETS: [(usize, [StageCost; 5]); 4] = [
+    (
+        1,
+        [
+            StageCost::new(31, 7, 0),
+            StageCost::new(22_100, 0, 0),
+            StageCost::new(908, 1_010, 3),
+            StageCost::new(23_400, 775, 388),
+            StageCost::new(9_710, 3, 2),
+        ],
+    ),
+    (
+        4,
+        [
+            StageCost::new(31, 7, 0),
+            StageCost::new(22_100, 0, 0),
+            StageCost::new(2_720, 1_930, 3),
+            StageCost::new(23_400, 775, 388),
+            StageCost::new(9_710, 3, 2),
+        ],
+    ),
+    (
+        14,
+        [
+            StageCost::new(31, 7, 0),
+            StageCost::new(22_100, 0, 0),
+            StageCost::new(8_820, 5_040, 3),
+            StageCost::new(23_400, 775, 388),
+            StageCost::new(9_710, 3, 2),
+        ],
+    ),
+    (
+        32,
+        [
+            StageCost::new(31, 7, 0),
+            StageCost::new(22_100, 0, 0),
+            StageCost::new(19_600, 10_600, 3),
+            StageCost::new(23_400, 775, 388),
+            StageCost::new(9_710, 3, 2),
+        ],
+    ),
+];
+
+/// Run `stage` and return its result along with the field operations it took
+fn measure<T>(stage: impl FnOnce() -> T) -> (T, StageCost) {
+    op_count::reset();
+    let result = stage();
+    let cost = StageCost::new(
+        op_count::fq_muls(),
+        op_count::fq_squares(),
+        op_count::fq_inversions(),
+    );
+    (result, cost)
+}
+
+/// A valid proof with `input_count` public inputs
+///
+/// The inputs are full-width scalars, so the MSM cost is not flattered by
+/// short inputs.
+fn full_width_proof(env: &Env, input_count: usize) -> (ProofData, VerifyingKey, Vec<Bytes>) {
+    let ic_logs: std::vec::Vec<Fr> = (0..=input_count as u64)
+        .map(|i| Fr::from_u64(i + 3))
+        .collect();
+    let inputs: std::vec::Vec<Fr> = (0..input_count as u64)
+        .map(|i| Fr::from_u64(i + 2).inverse().unwrap().neg())
+        .collect();
+    synthetic_proof(env, &ic_logs, &inputs)
+}
+
+/// Run `check_proof` one stage at a time on a valid proof with
+/// `input_count` full-width public inputs
+fn verification_stage_costs(input_count: usize) -> [StageCost; 5] {
+    let env = Env::default();
+    env.budget().reset_unlimited();
+
+    let (proof_data, vk, public_inputs) = full_width_proof(&env, input_count);
+    assert_eq!(
+        Groth16Verifier::check_proof(&env, &proof_data, &vk, &public_inputs),
+        Ok(())
+    );
+
+    // Decoding runs the subgroup checks, which are measured again on their
+    // own and taken out of the decode stage
+    let ((proof, key), decode) = measure(|| {
+        (
+            validated::Proof::decode(&env, &proof_data).unwrap(),
+            validated::Key::decode(&env, &vk).unwrap(),
+        )
+    });
+    let (in_g2, subgroup) = measure(|| {
+        [proof.b, key.beta, key.gamma, key.delta]
+            .iter()
+            .all(|p| validated::check_g2(p).is_ok())
+    });
+    assert!(in_g2);
+
+    let (vk_x, msm) =
+        measure(|| validated::input_commitment(&env, &vk.ic, &public_inputs).unwrap());
+    let pairs = Groth16Verifier::groth16_pairs(&proof, &key, &vk_x);
+    let (f, miller_loop) = measure(|| pairing::multi_miller_loop(&pairs));
+    let (result, final_exponentiation) = measure(|| pairing::final_exponentiation(&f));
+    assert!(result.is_one());
+
+    [
+        decode.sub(&subgroup),
+        subgroup,
+        msm,
+        miller_loop,
+        final_exponentiation,
+    ]
+}
+
+#[test]
+fn test_proof_verification_gas_usage() {
+    let mut exceeded = std::vec::Vec::new();
+
+    for (input_count, budgets) in STAGE_BUDGETS.iter() {
+        let costs = verification_stage_costs(*input_count);
+
+        for ((stage, cost), budget) in STAGES.iter().zip(costs.iter()).zip(budgets.iter()) {
+            std::println!("{:>2} inputs, {:<20} {:?}", input_count, stage, cost);
+
+            for ((name, value), (_, limit)) in cost.fields().iter().zip(budget.fields().iter()) {
+                if value > limit {
+                    exceeded.push(std::format!(
+                        "{} inputs, {}: {} {} > {}",
+                        input_count,
+                        stage,
+                        value,
+                        name,
+                        limit
+                    ));
+                }
+            }
+        }
+    }
+
+    assert!(
+        exceeded.is_empty(),
+        "stage budgets exceeded:\n{}",
+        exceeded.join("\n")
+    );
+}
+
+/// The release build, as `cargo build --release --target wasm32v1-none`
+/// writes it
+///
+/// wasm32v1-none keeps to the WASM features the Soroban host accepts;
+/// wasm32-unknown-unknown enables reference types since Rust 1.82, which
+/// the host rejects.
+const RELEASE_WASM: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/target/wasm32v1-none/release/soroban_groth16_verifier.wasm"
+);
+
+/// The release build with the `stage-bench` feature, as
+/// `cargo build --release --target wasm32v1-none --features stage-bench
+/// --target-dir target/stage-bench` writes it
+const STAGE_BENCH_WASM: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/target/stage-bench/wasm32v1-none/release/soroban_groth16_verifier.wasm"
+);
+
+/// Metered cost in the WASM VM, as (cpu insns, mem bytes)
+type WasmCost = (u64, u64);
+
+/// Upper bounds on each stage in the `stage-bench` WASM, per number of
+/// public inputs, in `STAGES` order
+///
+/// Like `STAGE_BUDGETS`, each bound is the measured cost plus about 10%.
+/// The WASM is built with the release profile, so the numbers move with the
+/// compiler as well as with the code. Only decoding and the MSM touch host
+/// objects; the arithmetic stages run on the stack and are held to 0 bytes.
+const WASM_STAGE_BUDGETS: [(usize, [WasmCost; 5]); 4] = [
+    (
+        1,
+        [
+            (2_110_000, 1_160),
+            (1_100_000_000, 0),
+            (93_500_000, 264),
+            (2_260_000_000, 0),
+            (631_000_000, 0),
+        ],
+    ),
+    (
+        4,
+        [
+            (2_150_000, 1_400),
+            (1_100_000_000, 0),
+            (213_000_000, 581),
+            (2_260_000_000, 0),
+            (631_000_000, 0),
+        ],
+    ),
+    (
+        14,
+        [
+            (2_260_000, 2_190),
+            (1_100_000_000, 0),
+            (616_000_000, 1_640),
+            (2_260_000_000, 0),
+            (631_000_000, 0),
+        ],
+    ),
+    (
+        32,
+        [
+            (2_470_000, 3_610),
+            (1_100_000_000, 0),
+            (1_330_000_000, 3_540),
+            (2_260_000_000, 0),
+            (631_000_000, 0),
+        ],
+    ),
+];
+
+/// Upper bounds on a full `verify_proof` in the release WASM, per number of
+/// public inputs
+///
+/// The measured cost plus about 10%. This is far above the network's limit
+/// of 100M cpu insns per transaction, so `verify_proof` cannot run on-chain
+/// yet; the bounds keep the cost from growing while it is brought down.
+const WASM_BUDGETS: [(usize, WasmCost); 4] = [
+    (1, (4_090_000_000, 3_600_000)),
+    (4, (4_210_000_000, 3_600_000)),
+    (14, (4_610_000_000, 3_600_000)),
+    (32, (5_320_000_000, 3_610_000)),
+];
+
+fn read_wasm(path: &str) -> std::vec::Vec<u8> {
+    std::fs::read(path).unwrap_or_else(|error| panic!("cannot read {}: {}", path, error))
+}
+
+/// Metered cost of `invoke`, from a fresh budget
+fn wasm_cost(env: &Env, invoke: impl FnOnce()) -> WasmCost {
+    env.budget().reset_unlimited();
+    invoke();
+    (
+        env.budget().cpu_instruction_cost(),
+        env.budget().memory_bytes_cost(),
+    )
+}
+
+/// Cost of each stage of a verification with `input_count` full-width
+/// public inputs, in the `stage-bench` WASM
+///
+/// `bench_stages` is called once per prefix of the stages, and each stage
+/// costs the difference between the calls that stop after it and before
+/// it. As in `verification_stage_costs`, the subgroup checks are taken out
+/// of the decode stage.
+fn wasm_stage_costs(wasm: &[u8], input_count: usize) -> [WasmCost; 5] {
+    let env = Env::default();
+    let contract_id = env.register_contract_wasm(None, wasm);
+    let (proof, vk, public_inputs) = full_width_proof(&env, input_count);
+    let bench_stages = Symbol::new(&env, "bench_stages");
+
+    let prefixes: [WasmCost; 6] = core::array::from_fn(|stages| {
+        let args = (
+            proof.clone(),
+            vk.clone(),
+            public_inputs.clone(),
+            stages as u32,
+        );
+        wasm_cost(&env, || {
+            env.invoke_contract::<()>(&contract_id, &bench_stages, args.into_val(&env))
+        })
+    });
+    let step = |stage: usize| {
+        let (before, after) = (prefixes[stage], prefixes[stage + 1]);
+        (after.0 - before.0, after.1.saturating_sub(before.1))
+    };
+
+    let (decode, subgroup) = (step(0), step(1));
+    [
+        (decode.0 - subgroup.0, decode.1.saturating_sub(subgroup.1)),
+        subgroup,
+        step(2),
+        step(3),
+        step(4),
+    ]
+}
+
+/// Describe every measured cost above its bound
+fn exceeded_wasm_costs(
+    label: &str,
+    cost: &WasmCost,
+    budget: &WasmCost,
+) -> std::vec::Vec<std::string::String> {
+    let mut exceeded = std::vec::Vec::new();
+    if cost.0 > budget.0 {
+        exceeded.push(std::format!(
+            "{}: {} cpu insns > {}",
+            label,
+            cost.0,
+            budget.0
+        ));
+    }
+    if cost.1 > budget.1 {
+        exceeded.push(std::format!(
+            "{}: {} mem bytes > {}",
+            label,
+            cost.1,
+            budget.1
+        ));
+    }
+    exceeded
+}
+
+/// Metered cost of each verification stage in the WASM VM
+///
+/// Build the `stage-bench` WASM first; CI runs this after building it.
+#[test]
+#[ignore = "needs the stage-bench WASM, see STAGE_BENCH_WASM"]
+fn test_wasm_stage_budget() {
+    let wasm = read_wasm(STAGE_BENCH_WASM);
+    let mut exceeded = std::vec::Vec::new();
+
+    for (input_count, budgets) in WASM_STAGE_BUDGETS.iter() {
+        let costs = wasm_stage_costs(&wasm, *input_count);
+
+        for ((stage, cost), budget) in STAGES.iter().zip(costs.iter()).zip(budgets.iter()) {
+            std::println!(
+                "{:>2} inputs, wasm {:<20} {} cpu insns, {} mem bytes",
+                input_count,
+                stage,
+                cost.0,
+                cost.1
+            );
+            let label = std::format!("{} inputs, {}", input_count, stage);
+            exceeded.extend(exceeded_wasm_costs(&label, cost, budget));
+        }
+    }
+
+    assert!(
+        exceeded.is_empty(),
+        "wasm stage budgets exceeded:\n{}",
+        exceeded.join("\n")
+    );
+}
+
+/// Metered cost of a full `verify_proof` in the release WASM
+///
+/// Build the release WASM first; CI runs this after its WASM build step.
+#[test]
+#[ignore = "needs the release WASM: cargo build --release --target wasm32v1-none"]
+fn test_wasm_verification_budget() {
+    let wasm = read_wasm(RELEASE_WASM);
+    let mut exceeded = std::vec::Vec::new();
+
+    for (input_count, budget) in WASM_BUDGETS.iter() {
+        let env = Env::default();
+        let contract_id = env.register_contract_wasm(None, wasm.as_slice());
+        let client = Groth16VerifierClient::new(&env, &contract_id);
+        let (proof, vk, public_inputs) = full_width_proof(&env, *input_count);
+
+        let cost = wasm_cost(&env, || {
+            assert!(client.verify_proof(&proof, &vk, &public_inputs))
+        });
+        std::println!(
+            "{:>2} inputs, wasm verify_proof: {} cpu insns, {} mem bytes",
+            input_count,
+            cost.0,
+            cost.1
+        );
+        let label = std::format!("{} inputs", input_count);
+        exceeded.extend(exceeded_wasm_costs(&label, &cost, budget));
+    }
+
+    assert!(
+        exceeded.is_empty(),
+        "wasm budgets exceeded:\n{}",
+        exceeded.join("\n")
+    );
+}
+
+/// The shared Miller loop saves Fq12 squarings over separate loops
+///
+/// The saving is asserted in Fq operations rather than with
+/// `env.budget().cpu_instruction_cost()`: the loops run natively here, and
+/// the host budget only meters the host calls, so it reads the same for
+/// either loop. The metered cost of the shared loop in the release WASM is
+/// bounded by the "miller loop" row of `WASM_STAGE_BUDGETS`.
+#[test]
+fn test_multi_miller_loop_budget() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Groth16Verifier);
+    let client = Groth16VerifierClient::new(&env, &contract_id);
+
+    // Full verification: host budget plus the Fq multiplication count,
+    // since natively compiled contract arithmetic is not metered
+    let ic_logs = [Fr::from_u64(3), Fr::from_u64(5)];
+    let (proof, vk, public_inputs) = synthetic_proof(&env, &ic_logs, &[Fr::from_u64(7)]);
+    env.budget().reset_default();
+    op_count::reset();
+    assert!(client.verify_proof(&proof, &vk, &public_inputs));
+    std::println!(
+        "verify_proof: {} host cpu insns, {} Fq muls, {} Fq squarings, {} Fq inversions",
+        env.budget().cpu_instruction_cost(),
+        op_count::fq_muls(),
+        op_count::fq_squares(),
+        op_count::fq_inversions()
+    );
+
+    // The four Groth16 pairs: separate Miller loops vs one shared loop
+    let g1 = G1Affine::generator();
+    let g2 = G2Affine::generator();
+    let pairs = [2u64, 3, 5, 7].map(|k| (g1.mul(&Fr::from_u64(k)), g2.mul(&Fr::from_u64(k + 10))));
+
+    op_count::reset();
+    let mut separate = Fq12::one();
+    for (p, q) in pairs.iter() {
+        separate = separate.mul(&pairing::miller_loop(p, q));
+    }
+    let separate_muls = op_count::fq_muls();
+
+    op_count::reset();
+    let shared = pairing::multi_miller_loop(&pairs);
+    let shared_muls = op_count::fq_muls();
+
+    std::println!(
+        "4-pair Miller loop: {} Fq muls separate, {} shared ({} saved)",
+        separate_muls,
+        shared_muls,
+        separate_muls - shared_muls
+    );
+    assert_eq!(shared, separate);
+
+    // Three of the four accumulator squarings go away on each of the 65
+    // loop digits; the affine line steps (one inversion each) are unchanged
+    op_count::reset();
+    let _ = shared.square();
+    let square_muls = op_count::fq_muls();
+    assert!(separate_muls - shared_muls >= 3 * 65 * square_muls);
 }
 
 #[test]
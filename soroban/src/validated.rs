@@ -0,0 +1,213 @@
+// Decode-Once Input Validation
+// The entrypoints take every point and scalar as big-endian `Bytes`. This
+// module decodes them exactly once into the types below, running every
+// check on the way: byte lengths, canonical encodings, identity points, and
+// curve and subgroup membership. The types can only be built here, so the
+// arithmetic stages work on decoded values and cannot skip a check.
+
+use soroban_sdk::{Bytes, Env, Vec};
+
+use crate::curve::{G1Affine, G1Projective, G2Affine};
+use crate::fq12::Fq12;
+use crate::msm::g1_msm;
+use crate::pairing::{LineCoeffs, MILLER_LINE_COUNT};
+use crate::{
+    G1Point, Groth16Verifier, PreparedVerifyingKey, ProofData, VerifierError, VerifyingKey,
+    MSM_BATCH,
+};
+
+/// Proof with A and C finite points of G1 and B a finite point of G2
+pub(crate) struct Proof {
+    pub a: G1Affine,
+    pub b: G2Affine,
+    pub c: G1Affine,
+    _validated: (),
+}
+
+impl Proof {
+    /// Decode and validate a proof
+    ///
+    /// Honest provers blind A, B and C with fresh randomness, so none of
+    /// them is ever the identity.
+    pub fn decode(env: &Env, proof: &ProofData) -> Result<Self, VerifierError> {
+        if !Groth16Verifier::validate_proof_structure(env, proof) {
+            return Err(VerifierError::MalformedProof);
+        }
+
+        // Every encoding is checked before any point, so each proof has
+        // exactly one accepted byte encoding
+        let a = Groth16Verifier::bytes_to_g1affine(env, &proof.pi_a)?;
+        let b = Groth16Verifier::bytes_to_g2affine(env, &proof.pi_b)?;
+        let c = Groth16Verifier::bytes_to_g1affine(env, &proof.pi_c)?;
+
+        if a.is_infinity() || b.is_infinity() || c.is_infinity() {
+            return Err(VerifierError::IdentityPoint);
+        }
+        check_g1(&a)?;
+        check_g2(&b)?;
+        check_g1(&c)?;
+
+        Ok(Proof {
+            a,
+            b,
+            c,
+            _validated: (),
+        })
+    }
+}
+
+/// α, β, γ, δ of a verifying key, all finite points of their groups
+///
+/// The IC points are decoded along with the public inputs by
+/// `input_commitment`, which needs them one batch at a time.
+pub(crate) struct Key {
+    pub alpha: G1Affine,
+    pub beta: G2Affine,
+    pub gamma: G2Affine,
+    pub delta: G2Affine,
+    _validated: (),
+}
+
+impl Key {
+    /// Decode and validate α, β, γ, δ, and the shape of IC
+    ///
+    /// α, β, γ, δ at infinity make the key degenerate (γ = O drops the
+    /// public inputs from the equation).
+    pub fn decode(env: &Env, vk: &VerifyingKey) -> Result<Self, VerifierError> {
+        if !Groth16Verifier::validate_vk_structure(env, vk) {
+            return Err(VerifierError::MalformedVerifyingKey);
+        }
+
+        let alpha = Groth16Verifier::bytes_to_g1affine(env, &vk.alpha)?;
+        let beta = Groth16Verifier::bytes_to_g2affine(env, &vk.beta)?;
+        let gamma = Groth16Verifier::bytes_to_g2affine(env, &vk.gamma)?;
+        let delta = Groth16Verifier::bytes_to_g2affine(env, &vk.delta)?;
+
+        if alpha.is_infinity() || [beta, gamma, delta].iter().any(|p| p.is_infinity()) {
+            return Err(VerifierError::IdentityPoint);
+        }
+        check_g1(&alpha)?;
+        for point in [beta, gamma, delta].iter() {
+            check_g2(point)?;
+        }
+
+        Ok(Key {
+            alpha,
+            beta,
+            gamma,
+            delta,
+            _validated: (),
+        })
+    }
+}
+
+/// Decoded e(α, β) and Miller-loop lines of a prepared verifying key
+///
+/// Like the key in `verify_proof`, these are trusted to come from
+/// `prepare_verifying_key`: checking them against the key would redo the
+/// work they save, so only their encoding is validated.
+pub(crate) struct PreparedKey {
+    pub alpha_beta: Fq12,
+    pub gamma_lines: [LineCoeffs; MILLER_LINE_COUNT],
+    pub delta_lines: [LineCoeffs; MILLER_LINE_COUNT],
+    _validated: (),
+}
+
+impl PreparedKey {
+    /// Decode a prepared key, and check the shape of its IC
+    pub fn decode(env: &Env, pvk: &PreparedVerifyingKey) -> Result<Self, VerifierError> {
+        if !Groth16Verifier::validate_prepared_vk_structure(env, pvk) {
+            return Err(VerifierError::MalformedVerifyingKey);
+        }
+
+        Ok(PreparedKey {
+            alpha_beta: Groth16Verifier::bytes_to_fq12(&pvk.alpha_beta)?,
+            gamma_lines: Groth16Verifier::bytes_to_lines(&pvk.gamma_lines)?,
+            delta_lines: Groth16Verifier::bytes_to_lines(&pvk.delta_lines)?,
+            _validated: (),
+        })
+    }
+}
+
+/// Decode an IC point, which may be the identity (e.g. for an unused input
+/// wire) but must be on the curve
+pub(crate) fn ic_point(env: &Env, point: &G1Point) -> Result<G1Affine, VerifierError> {
+    let point = Groth16Verifier::bytes_to_g1affine(env, point)?;
+    check_g1(&point)?;
+    Ok(point)
+}
+
+/// L = IC[0] + Σ public_input[i] * IC[i + 1], a point of G1
+///
+/// Decodes each IC point and public input exactly once. Without a heap they
+/// cannot all be held at once, so they are decoded and combined
+/// `MSM_BATCH` at a time, in Jacobian coordinates so the whole sum costs a
+/// single inversion.
+pub(crate) fn input_commitment(
+    env: &Env,
+    ic: &Vec<G1Point>,
+    public_inputs: &Vec<Bytes>,
+) -> Result<G1Affine, VerifierError> {
+    if public_inputs.len() + 1 != ic.len() {
+        return Err(VerifierError::PublicInputCountMismatch);
+    }
+
+    let mut result = G1Projective::from_affine(&ic_point(env, &ic.get(0).unwrap())?);
+
+    let mut points = [G1Affine::infinity(); MSM_BATCH];
+    let mut scalars = [[0u64; 4]; MSM_BATCH];
+    let mut len = 0;
+
+    for (point, input) in ic.iter().skip(1).zip(public_inputs.iter()) {
+        points[len] = ic_point(env, &point)?;
+        scalars[len] = Groth16Verifier::bytes_to_scalar(&input)?.to_limbs();
+        len += 1;
+
+        if len == MSM_BATCH {
+            result = result.add(&g1_msm(&points, &scalars));
+            len = 0;
+        }
+    }
+    result = result.add(&g1_msm(&points[..len], &scalars[..len]));
+
+    Ok(result.to_affine())
+}
+
+/// Check that there is one public input per IC point after the first, each
+/// a canonical scalar, without combining them
+///
+/// For callers that fold the inputs of several proofs before their MSM.
+pub(crate) fn check_inputs(
+    ic: &Vec<G1Point>,
+    public_inputs: &Vec<Bytes>,
+) -> Result<(), VerifierError> {
+    if public_inputs.len() + 1 != ic.len() {
+        return Err(VerifierError::PublicInputCountMismatch);
+    }
+    for input in public_inputs.iter() {
+        Groth16Verifier::bytes_to_scalar(&input)?;
+    }
+    Ok(())
+}
+
+/// On the curve, hence in G1, whose cofactor is 1
+fn check_g1(point: &G1Affine) -> Result<(), VerifierError> {
+    if !point.is_on_curve() {
+        return Err(VerifierError::NotOnCurve);
+    }
+    Ok(())
+}
+
+/// On the twist and in the prime-order subgroup
+///
+/// Without the subgroup check, an attacker could provide points from a
+/// different subgroup, breaking Groth16 soundness.
+pub(crate) fn check_g2(point: &G2Affine) -> Result<(), VerifierError> {
+    if !point.is_on_curve() {
+        return Err(VerifierError::NotOnCurve);
+    }
+    if !point.is_in_correct_subgroup() {
+        return Err(VerifierError::NotInSubgroup);
+    }
+    Ok(())
+}